@@ -1,10 +1,15 @@
 #import bevy_pbr::forward_io::VertexOutput
 
 struct PortalSettings {
+    tint: vec4<f32>,
     time: f32,
-    _pad1: f32,
-    _pad2: f32,
-    _pad3: f32,
+    fallback: f32,
+    shape: f32,      // 0 rectangle, 1 arch, 2 circle, 3 moon gate
+    distortion: f32,
+    edge_glow: f32,
+    aspect: f32,     // door width / height
+    locked: f32,     // 1 = sealed, fading to 0 as the door unlocks
+    denied: f32,     // flare when the player walks into the seal
 }
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> settings: PortalSettings;
@@ -20,6 +25,9 @@ const RED_BRIGHT: vec3<f32> = vec3<f32>(0.86, 0.08, 0.24);
 const GOLD: vec3<f32> = vec3<f32>(1.0, 0.84, 0.0);
 const GOLD_DARK: vec3<f32> = vec3<f32>(0.72, 0.53, 0.04);
 
+// Moon gate threshold, as a fraction of the radius below the centre
+const MOON_GATE_SILL: f32 = 0.8;
+
 fn hash(p: vec2<f32>) -> f32 {
     return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
 }
@@ -78,15 +86,70 @@ fn red_tendrils(uv: vec2<f32>, edge: f32, t: f32) -> f32 {
     return e * 0.3;
 }
 
+// Distance inside the opening's outline - UV units for the rectangle, door
+// widths for the round shapes (whose meshes already cut the outline)
+fn shape_edge(uv: vec2<f32>) -> f32 {
+    if settings.shape < 0.5 {
+        return min(min(uv.x, 1.0 - uv.x), min(uv.y, 1.0 - uv.y));
+    }
+    let p = vec2<f32>(uv.x - 0.5, (0.5 - uv.y) / settings.aspect);
+    let r = 0.5;
+    let floor_edge = p.y + 0.5 / settings.aspect;
+    if settings.shape < 1.5 {
+        let head = 0.5 / settings.aspect - r;
+        var side = r - abs(p.x);
+        if p.y > head {
+            side = r - length(p - vec2<f32>(0.0, head));
+        }
+        return min(side, floor_edge);
+    }
+    // Round shapes stand on the floor: the circle touches it, the moon gate's
+    // sill lies on it
+    var lift = r;
+    if settings.shape > 2.5 {
+        lift = r * MOON_GATE_SILL;
+    }
+    let circle = r - length(p - vec2<f32>(0.0, lift - 0.5 / settings.aspect));
+    if settings.shape < 2.5 {
+        return circle;
+    }
+    return min(circle, floor_edge);
+}
+
+// Middle of the opening, in the same door-width units as shape_edge
+fn shape_center() -> vec2<f32> {
+    let ground = -0.5 / settings.aspect;
+    if settings.shape > 2.5 {
+        return vec2<f32>(0.0, ground + 0.5 * MOON_GATE_SILL);
+    }
+    if settings.shape > 1.5 {
+        return vec2<f32>(0.0, ground + 0.5);
+    }
+    return vec2<f32>(0.0);
+}
+
+// Closed door: slow lacquer swirl behind a ring of seal glyphs
+fn seal(uv: vec2<f32>, t: f32, tint: vec3<f32>) -> vec3<f32> {
+    let p = vec2<f32>(uv.x - 0.5, (0.5 - uv.y) / settings.aspect) - shape_center();
+    let r = length(p);
+    let a = atan2(p.y, p.x);
+    let swirl = fbm(vec2<f32>(a * 2.0 + r * 6.0 - t * 0.15, r * 4.0 - t * 0.1));
+    var col = mix(RED_DEEP * 0.35, RED_DEEP, swirl) * tint;
+    let ring = smoothstep(0.015, 0.0, abs(r - 0.3));
+    let glyphs = step(0.5, fract(a * 12.0 / TAU)) * smoothstep(0.05, 0.0, abs(r - 0.36));
+    col += mix(GOLD_DARK, GOLD, sin(t * 1.2) * 0.5 + 0.5) * tint * (ring + glyphs * 0.6);
+    return col;
+}
+
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let uv = in.uv;
     let time = settings.time;
     
+    let tint = settings.tint.rgb;
+
     // Edge distance
-    let edge_x = min(uv.x, 1.0 - uv.x);
-    let edge_y = min(uv.y, 1.0 - uv.y);
-    let edge = min(edge_x, edge_y);
+    let edge = max(shape_edge(uv), 0.0);
     
     let center = vec2<f32>(0.5, 0.5);
     let to_center = uv - center;
@@ -94,42 +157,50 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     let angle = atan2(to_center.y, to_center.x);
     
     // Ink-like liquid distortion
-    let flow = ink_flow(uv, time);
+    let flow = ink_flow(uv, time) * settings.distortion;
     let edge_factor = smoothstep(0.0, 0.12, edge);
     var sample_uv = uv + flow * edge_factor;
-    sample_uv += to_center * (1.0 - edge_factor) * 0.012;
+    sample_uv += to_center * (1.0 - edge_factor) * 0.012 * settings.distortion;
     sample_uv = clamp(sample_uv, vec2(0.005), vec2(0.995));
     
     // Sample with red-shifted chromatic aberration
-    let shift = (1.0 - edge_factor) * 0.003;
+    let shift = (1.0 - edge_factor) * 0.003 * settings.distortion;
     let r = textureSample(portal_tex, portal_samp, sample_uv + vec2(shift, 0.0)).r;
     let g = textureSample(portal_tex, portal_samp, sample_uv).g;
     let b = textureSample(portal_tex, portal_samp, sample_uv - vec2(shift * 0.5, 0.0)).b;
     var color = vec3<f32>(r, g, b);
+
+    // Past the recursion depth: a slow red haze instead of the view
+    let haze = mix(RED_DEEP, GOLD_DARK, fbm(uv * 3.0 + vec2(time * 0.1, -time * 0.08)) * 0.5) * 0.5 * tint;
+    color = mix(color, haze, settings.fallback);
     
+    // Sealed: the view dissolves behind the seal as the door unlocks
+    let seal_mask = smoothstep(settings.locked - 0.1, settings.locked + 0.1, 0.9 - fbm(uv * 4.0) * 0.8);
+    color = mix(color, seal(uv, time, tint) * (1.0 + settings.denied * 2.0), 1.0 - seal_mask);
+
     // Red liquid surface highlights
     let highlight = pow(fbm(uv * 6.0 + vec2(time * 0.2, time * 0.15)), 2.0) * 0.1 * edge_factor;
-    color += RED_BRIGHT * highlight;
+    color += RED_BRIGHT * tint * highlight;
     
     // Border glow - deep red with gold accents
     let border_w = 0.06;
     let border = 1.0 - smoothstep(0.0, border_w, edge);
     let border_phase = time * 1.2 + angle * 2.0;
-    let border_col = mix(RED_DEEP, GOLD_DARK, sin(border_phase) * 0.3 + 0.3);
+    let border_col = mix(RED_DEEP, GOLD_DARK, sin(border_phase) * 0.3 + 0.3) * tint;
     
     // Red tendrils
-    let tend = red_tendrils(uv, edge, time);
+    let tend = red_tendrils(uv, edge, time) * settings.edge_glow;
     
     // Circular energy rings
     let rings = energy_rings(uv, time);
     
     // Pulse
     let pulse = sin(time * 1.8) * 0.1 + 0.9;
-    let glow = border * pulse * 2.0;
+    let glow = border * pulse * 2.0 * settings.edge_glow;
     
     // Gold sparkles
     let spark_uv = uv * 40.0 + vec2(time * 1.5, time * 1.2);
-    let spark = step(0.97, hash(floor(spark_uv))) * border * 2.0;
+    let spark = step(0.97, hash(floor(spark_uv))) * border * 2.0 * settings.edge_glow;
     
     // Inner red flow
     let inner = smoothstep(0.3, 0.06, dist) * fbm(uv * 3.5 + vec2(time * 0.3, -time * 0.2)) * 0.06;
@@ -138,10 +209,10 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     var final_color = color;
     final_color = mix(final_color, border_col, glow * 0.4);
     final_color += border_col * glow * 0.4;
-    final_color += mix(RED_BRIGHT, GOLD, 0.3) * tend;
-    final_color += GOLD * spark;
-    final_color += RED_DEEP * inner;
-    final_color += mix(RED_BRIGHT, GOLD, 0.5) * rings * 0.15;
+    final_color += mix(RED_BRIGHT, GOLD, 0.3) * tint * tend;
+    final_color += GOLD * tint * spark;
+    final_color += RED_DEEP * tint * inner;
+    final_color += mix(RED_BRIGHT, GOLD, 0.5) * tint * rings * 0.15;
     
     // Edge fade
     final_color *= smoothstep(0.0, 0.006, edge);
@@ -1,7 +1,17 @@
-#import bevy_pbr::forward_io::VertexOutput
+// Portal transition overlay - full-screen UI material. `direction` 1 covers
+// the view (approaching a door), -1 reveals the new room after crossing.
+// `style`: 0 = fade, 1 = dissolve, 2 = radial wipe with swirling energy.
 
-@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> progress: f32;
-@group(#{MATERIAL_BIND_GROUP}) @binding(1) var<uniform> direction: f32;
+#import bevy_ui::ui_vertex_output::UiVertexOutput
+
+struct TransitionSettings {
+    progress: f32,
+    direction: f32,
+    style: f32,
+    time: f32,
+}
+
+@group(1) @binding(0) var<uniform> settings: TransitionSettings;
 
 const PI: f32 = 3.14159265359;
 
@@ -9,38 +19,47 @@ fn hash(p: vec2<f32>) -> f32 {
     return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
 }
 
+fn noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    return mix(mix(hash(i), hash(i + vec2(1.0, 0.0)), u.x),
+               mix(hash(i + vec2(0.0, 1.0)), hash(i + vec2(1.0, 1.0)), u.x), u.y);
+}
+
 @fragment
-fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+fn fragment(in: UiVertexOutput) -> @location(0) vec4<f32> {
     let uv = in.uv;
-    let center = vec2<f32>(0.5, 0.5);
-    let to_center = uv - center;
-    let dist = length(to_center);
+    let aspect = in.size.x / max(in.size.y, 1.0);
+    let to_center = (uv - vec2<f32>(0.5, 0.5)) * vec2<f32>(aspect, 1.0);
+    let dist = length(to_center) / max(length(vec2<f32>(aspect, 1.0)) * 0.5, 0.001);
     let angle = atan2(to_center.y, to_center.x);
-    
-    let p = smoothstep(0.0, 1.0, progress);
-    
-    // Radial wipe
-    let wipe_radius = select(p * 1.2, (1.0 - p) * 1.2, direction > 0.0);
-    let wipe = smoothstep(wipe_radius - 0.15, wipe_radius + 0.05, dist);
-    
+    let direction = settings.direction;
+
+    let p = smoothstep(0.0, 1.0, settings.progress);
+
     // Swirling energy
-    let swirl = sin(angle * 6.0 + p * PI * 4.0 * direction) * 0.5 + 0.5;
-    
-    // Edge glow
-    let edge_glow = smoothstep(wipe_radius - 0.08, wipe_radius, dist) * 
-                    smoothstep(wipe_radius + 0.15, wipe_radius, dist);
-    
-    // Sparkles
-    let sparkle = step(0.96, hash(uv * 30.0 + vec2(p * 20.0, 0.0))) * edge_glow * 4.0;
-    
-    // Energy color
+    let swirl = sin(angle * 6.0 + p * PI * 4.0 * direction + settings.time * 0.5) * 0.5 + 0.5;
     let energy = mix(vec3<f32>(0.15, 0.08, 0.5), vec3<f32>(0.5, 0.35, 0.95), swirl);
-    
-    let base_alpha = (1.0 - wipe) * p * 0.85;
-    let glow_alpha = edge_glow * swirl * 0.6;
-    let total_alpha = clamp(base_alpha + glow_alpha + sparkle * 0.3, 0.0, 0.92);
-    
-    let final_color = energy + vec3<f32>(0.9, 0.8, 1.0) * sparkle;
-    
-    return vec4<f32>(final_color, total_alpha);
+
+    var alpha = p;
+    var glow = 0.0;
+
+    if settings.style > 1.5 {
+        // Radial wipe: closes in from the edges, opens from the centre
+        let radius = (1.0 - p) * 1.3 - 0.15;
+        alpha = smoothstep(radius - 0.1, radius + 0.05, dist);
+        glow = smoothstep(0.1, 0.0, abs(dist - radius)) * swirl * p;
+    } else if settings.style > 0.5 {
+        // Dissolve: noise threshold sweeps across the screen
+        let n = noise(uv * vec2<f32>(aspect, 1.0) * 12.0) * 0.7 + noise(uv * 40.0) * 0.3;
+        alpha = smoothstep(n - 0.05, n + 0.05, p * 1.1);
+        glow = smoothstep(0.08, 0.0, abs(n - p * 1.1)) * p;
+    }
+
+    // Sparkles along the moving edge
+    let sparkle = step(0.96, hash(floor(uv * 120.0) + vec2(floor(settings.time * 8.0), 0.0))) * glow;
+
+    let color = energy + vec3<f32>(0.9, 0.8, 1.0) * (glow * 0.6 + sparkle);
+    return vec4<f32>(color, clamp(alpha + glow * 0.4, 0.0, 1.0));
 }
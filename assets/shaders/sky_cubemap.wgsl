@@ -0,0 +1,64 @@
+// Cubemap sky - samples the room's cube texture by local mesh direction,
+// so skybox rotation (the mesh transform) turns the sky with it. Time-of-day
+// rooms crossfade into a second cubemap by `blend`.
+
+#import bevy_pbr::{
+    mesh_functions,
+    mesh_view_bindings::view,
+    view_transformations::position_world_to_clip,
+}
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping::tone_mapping
+#endif
+
+struct SkySettings {
+    exposure: f32,
+    blend: f32,
+    _pad2: f32,
+    _pad3: f32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var sky_texture: texture_cube<f32>;
+@group(#{MATERIAL_BIND_GROUP}) @binding(1) var sky_sampler: sampler;
+@group(#{MATERIAL_BIND_GROUP}) @binding(2) var<uniform> settings: SkySettings;
+@group(#{MATERIAL_BIND_GROUP}) @binding(3) var sky_texture_next: texture_cube<f32>;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) direction: vec3<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    let world_position = mesh_functions::mesh_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0),
+    );
+    out.clip_position = position_world_to_clip(world_position.xyz);
+    out.direction = vertex.position;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let dir = normalize(in.direction);
+    var color = textureSample(sky_texture, sky_sampler, dir);
+    if settings.blend > 0.0 {
+        color = mix(color, textureSample(sky_texture_next, sky_sampler, dir), settings.blend);
+    }
+    color = vec4<f32>(color.rgb * settings.exposure, 1.0);
+
+#ifdef TONEMAP_IN_SHADER
+    color = tone_mapping(color, view.color_grading);
+#endif
+
+    return color;
+}
@@ -5,11 +5,15 @@ mod content;
 mod ui;
 
 use bevy::prelude::*;
+use std::collections::HashSet;
 
 use animation::{animate_buttons, animate_panel};
 use content::PAGES;
 use ui::setup_book_ui;
 
+/// Chapters in the book, one per page (`ChapterRead` counts from 1)
+pub const CHAPTER_COUNT: usize = PAGES.len();
+
 pub struct BookReaderPlugin;
 
 impl Plugin for BookReaderPlugin {
@@ -22,9 +26,11 @@ impl Plugin for BookReaderPlugin {
                 (
                     toggle_book_input,
                     close_book_input,
+                    gamepad_book_input,
                     handle_tab_buttons,
                     handle_nav_buttons,
                     update_page_content.run_if(resource_changed::<BookState>),
+                    track_read_pages.run_if(resource_changed::<BookState>),
                     animate_panel,
                     animate_buttons,
                 ),
@@ -41,6 +47,8 @@ pub struct BookState {
     pub page: usize,
     pub tab: Tab,
     pub target_scale: f32,
+    /// Pages opened on the Book tab
+    pub pages_read: HashSet<usize>,
 }
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
@@ -122,6 +130,37 @@ fn close_book_input(keyboard: Res<ButtonInput<KeyCode>>, mut state: ResMut<BookS
     }
 }
 
+/// Select toggles the book; while open the D-pad turns pages (left/right) and
+/// switches tabs (up/down), and B closes it
+fn gamepad_book_input(gamepads: Query<&Gamepad>, mut state: ResMut<BookState>) {
+    for pad in gamepads.iter() {
+        if pad.just_pressed(GamepadButton::Select) {
+            state.open = !state.open;
+            state.target_scale = if state.open { 1.0 } else { 0.0 };
+            continue;
+        }
+        if !state.open {
+            continue;
+        }
+        if pad.just_pressed(GamepadButton::East) {
+            state.open = false;
+            state.target_scale = 0.0;
+        }
+        if pad.just_pressed(GamepadButton::DPadLeft) {
+            state.page = state.page.saturating_sub(1);
+        }
+        if pad.just_pressed(GamepadButton::DPadRight) {
+            state.page = (state.page + 1).min(PAGES.len() - 1);
+        }
+        if pad.just_pressed(GamepadButton::DPadUp) || pad.just_pressed(GamepadButton::DPadDown) {
+            state.tab = match state.tab {
+                Tab::Book => Tab::Character,
+                Tab::Character => Tab::Book,
+            };
+        }
+    }
+}
+
 fn handle_tab_buttons(
     mut state: ResMut<BookState>,
     mut tabs: Query<
@@ -239,3 +278,10 @@ fn update_page_content(
         }
     }
 }
+
+fn track_read_pages(mut state: ResMut<BookState>) {
+    let page = state.page.min(PAGES.len() - 1);
+    if state.open && state.tab == Tab::Book && !state.pages_read.contains(&page) {
+        state.pages_read.insert(page);
+    }
+}
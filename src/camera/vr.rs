@@ -1,16 +1,22 @@
 //! VR camera - syncs XrCamera state, no motion effects
 
+use bevy::camera::visibility::RenderLayers;
 use bevy::prelude::*;
 use bevy_mod_xr::camera::XrCamera;
 
 use super::CameraState;
 use crate::platform::on_vr;
+use crate::player::PlayerState;
+use crate::world::{room_view_layers, Eye};
 
 pub struct VrCameraPlugin;
 
 impl Plugin for VrCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, sync_vr_camera_state.run_if(on_vr));
+        app.add_systems(
+            Update,
+            (sync_vr_camera_state, sync_vr_eye_layers).run_if(on_vr),
+        );
     }
 }
 
@@ -26,6 +32,22 @@ fn sync_vr_camera_state(
     }
 }
 
+/// Each eye renders the current room plus its own half of a stereo sky
+/// (only the tour has rooms; other modes keep the default layer)
+fn sync_vr_eye_layers(
+    mut cmd: Commands,
+    player: Option<Res<PlayerState>>,
+    xr_cameras: Query<(Entity, &XrCamera, Option<&RenderLayers>)>,
+) {
+    let Some(player) = player else { return };
+    for (entity, camera, layers) in xr_cameras.iter() {
+        let wanted = room_view_layers(player.room, Eye::from_view(camera.0));
+        if layers != Some(&wanted) {
+            cmd.entity(entity).insert(wanted);
+        }
+    }
+}
+
 /// Marker for entities that follow VR head
 #[derive(Component)]
 pub struct FollowVrHead;
@@ -1,19 +1,33 @@
 //! WebXR camera - applies XR pose to Bevy camera
+//!
+//! With a stereo session the game camera draws the left eye on the left half
+//! of the canvas and a second camera the right eye on the right half, each
+//! rendering its own half of a stereo sky.
 
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::Viewport;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 
 use super::{CameraState, GameCamera};
 use crate::platform::on_webxr;
+use crate::player::PlayerState;
+use crate::world::{room_view_layers, Eye};
 
 #[cfg(feature = "webxr")]
-use crate::platform::{WebXrPose, WebXrState};
+use crate::platform::{WebXrPose, WebXrState, XrViewData};
 
 pub struct WebXrCameraPlugin;
 
 impl Plugin for WebXrCameraPlugin {
     fn build(&self, app: &mut App) {
         #[cfg(feature = "webxr")]
-        app.add_systems(Update, apply_webxr_camera.run_if(on_webxr));
+        app.add_systems(
+            Update,
+            (apply_webxr_camera, sync_webxr_eyes)
+                .chain()
+                .run_if(on_webxr),
+        );
     }
 }
 
@@ -57,3 +71,109 @@ fn apply_webxr_camera(
     camera_state.pitch = pitch;
     camera_state.reset_effects();
 }
+
+/// Right-eye camera of a stereo WebXR session (the game camera is the left)
+#[derive(Component)]
+struct WebXrEyeCamera;
+
+/// Split the canvas between the eyes and keep the right eye on the right
+/// sky layers, offset from the left eye as the XR views are
+#[cfg(feature = "webxr")]
+#[allow(clippy::type_complexity)]
+fn sync_webxr_eyes(
+    mut cmd: Commands,
+    state: Res<WebXrState>,
+    pose: Res<WebXrPose>,
+    player: Option<Res<PlayerState>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut left_q: Query<
+        (&Transform, &mut Camera, &mut Projection),
+        (With<GameCamera>, Without<WebXrEyeCamera>),
+    >,
+    mut right_q: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Camera,
+            &mut Projection,
+            Option<&RenderLayers>,
+        ),
+        With<WebXrEyeCamera>,
+    >,
+) {
+    let views = pose.0.lock().map(|d| d.views.clone()).unwrap_or_default();
+    let left_view = views.iter().find(|v| v.eye == Eye::Left);
+    let right_view = views.iter().find(|v| v.eye == Eye::Right);
+    let (Ok((left_tf, mut left_cam, mut left_proj)), Ok(window)) =
+        (left_q.single_mut(), window.single())
+    else {
+        return;
+    };
+
+    let (Some(left_view), Some(right_view), Some(player), true) =
+        (left_view, right_view, player, state.session_active)
+    else {
+        // Mono: the game camera fills the canvas again
+        if left_cam.viewport.is_some() {
+            left_cam.viewport = None;
+        }
+        for (entity, ..) in right_q.iter() {
+            cmd.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let size = window.physical_size();
+    let half = UVec2::new(size.x / 2, size.y);
+    let viewport = |x: u32| Viewport {
+        physical_position: UVec2::new(x, 0),
+        physical_size: half.max(UVec2::ONE),
+        ..default()
+    };
+    left_cam.viewport = Some(viewport(0));
+    set_fov(&mut left_proj, left_view);
+
+    // Right eye relative to the left one, carried over onto the game camera
+    let pose_of = |v: &XrViewData| {
+        Transform::from_translation(Vec3::from_array(v.position))
+            .with_rotation(Quat::from_array(v.orientation))
+    };
+    let relative =
+        pose_of(left_view).compute_affine().inverse() * pose_of(right_view).compute_affine();
+    let right_tf = Transform::from_matrix((left_tf.compute_affine() * relative).into());
+    let layers = room_view_layers(player.room, Eye::Right);
+
+    match right_q.single_mut() {
+        Ok((entity, mut tf, mut cam, mut proj, current)) => {
+            *tf = right_tf;
+            cam.viewport = Some(viewport(half.x));
+            set_fov(&mut proj, right_view);
+            if current != Some(&layers) {
+                cmd.entity(entity).insert(layers);
+            }
+        }
+        Err(_) => {
+            let mut proj = Projection::Perspective(default());
+            set_fov(&mut proj, right_view);
+            cmd.spawn((
+                Camera3d::default(),
+                Camera {
+                    order: left_cam.order + 1,
+                    viewport: Some(viewport(half.x)),
+                    ..default()
+                },
+                proj,
+                right_tf,
+                layers,
+                WebXrEyeCamera,
+            ));
+        }
+    }
+}
+
+#[cfg(feature = "webxr")]
+fn set_fov(projection: &mut Projection, view: &XrViewData) {
+    if let Projection::Perspective(ref mut p) = *projection {
+        p.fov = view.fov;
+    }
+}
@@ -7,26 +7,26 @@ use std::f32::consts::PI;
 
 use crate::camera::CameraState;
 use crate::ibl::IblLitModel;
-use crate::loading::ModelAssets;
 use crate::player::PlayerState;
-use crate::world::{room_center, TOTAL_ROOMS};
+use crate::room_streaming::RoomStreaming;
+use crate::tour::Tour;
+use crate::world::room_center;
 use crate::GameState;
 
 pub struct GlbCharacterPlugin;
 
 impl Plugin for GlbCharacterPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(CharacterConfig::default())
-            .add_systems(OnEnter(GameState::Viewing), spawn_characters)
-            .add_systems(
-                Update,
-                (
-                    mirage_illusion,
-                    breathing_animation,
-                    dynamic_character_lighting,
-                )
-                    .run_if(in_state(GameState::Viewing)),
-            );
+        app.insert_resource(CharacterConfig::default()).add_systems(
+            Update,
+            (
+                sync_room_characters,
+                mirage_illusion,
+                breathing_animation,
+                dynamic_character_lighting,
+            )
+                .run_if(in_state(GameState::Viewing)),
+        );
     }
 }
 
@@ -34,6 +34,7 @@ impl Plugin for GlbCharacterPlugin {
 pub struct CharacterConfig {
     pub base_scale: f32,
     pub position: Vec2,
+    pub barrier_radius: f32, // How close the player can walk up to the character
 }
 
 impl Default for CharacterConfig {
@@ -41,6 +42,7 @@ impl Default for CharacterConfig {
         Self {
             base_scale: 1.8,
             position: Vec2::new(0.0, -10.0),
+            barrier_radius: 3.7,
         }
     }
 }
@@ -53,20 +55,43 @@ pub struct RoomCharacter {
 }
 
 #[derive(Component)]
-pub struct CharacterLight;
+pub struct CharacterLight {
+    pub room: usize,
+}
 
-fn spawn_characters(
+/// Spawn characters as their rooms stream in, despawn them when rooms unload
+#[allow(clippy::too_many_arguments)]
+fn sync_room_characters(
     mut cmd: Commands,
-    models: Res<ModelAssets>,
+    tour: Res<Tour>,
+    streaming: Res<RoomStreaming>,
     gltfs: Res<Assets<Gltf>>,
     gltf_meshes: Res<Assets<GltfMesh>>,
     config: Res<CharacterConfig>,
+    characters: Query<(Entity, &RoomCharacter)>,
+    lights: Query<(Entity, &CharacterLight)>,
 ) {
-    let mdl_handles = [&models.modelo1, &models.modelo2, &models.modelo3];
-
-    for (room, mdl_handle) in mdl_handles.iter().enumerate().take(TOTAL_ROOMS) {
+    for room in 0..tour.room_count() {
+        let spawned = characters.iter().any(|(_, ch)| ch.room == room);
+        let Some(assets) = streaming.assets(room) else {
+            if spawned {
+                for (entity, _) in characters.iter().filter(|(_, ch)| ch.room == room) {
+                    cmd.entity(entity).despawn();
+                }
+                for (entity, _) in lights.iter().filter(|(_, light)| light.room == room) {
+                    cmd.entity(entity).despawn();
+                }
+            }
+            continue;
+        };
+        if spawned {
+            continue;
+        }
+        let Some(mdl_handle) = &assets.character else {
+            continue;
+        };
         let center = room_center(room);
-        if let Some(gltf) = gltfs.get(*mdl_handle) {
+        if let Some(gltf) = gltfs.get(mdl_handle) {
             let char_pos = center + Vec3::new(config.position.x, -1.3, config.position.y);
             let transform = Transform::from_translation(char_pos)
                 .with_scale(Vec3::splat(config.base_scale))
@@ -93,14 +118,11 @@ fn spawn_characters(
                 },
                 Transform::from_translation(char_pos + Vec3::Y * 2.0),
                 RenderLayers::layer(room),
-                CharacterLight,
+                CharacterLight { room },
             ));
+            info!("👤 Character spawned in {}", tour.rooms[room].name);
         }
     }
-    info!(
-        "👤 {} characters spawned with dynamic lighting",
-        TOTAL_ROOMS
-    );
 }
 
 fn spawn_gltf_model(
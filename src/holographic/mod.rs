@@ -6,7 +6,8 @@ use bevy::prelude::*;
 
 use crate::performance::QualitySettings;
 use crate::player::PlayerState;
-use crate::world::{room_center, TOTAL_ROOMS};
+use crate::tour::Tour;
+use crate::world::room_center;
 use crate::GameState;
 
 #[cfg(feature = "particles")]
@@ -58,6 +59,7 @@ fn spawn_holographic_particles(
     mut commands: Commands,
     mut effects: ResMut<Assets<EffectAsset>>,
     quality: Res<QualitySettings>,
+    tour: Res<Tour>,
 ) {
     let beings = create_light_beings_effect(&quality);
     let halos = create_halos_effect(&quality);
@@ -65,7 +67,7 @@ fn spawn_holographic_particles(
     let beings_handle = effects.add(beings);
     let halos_handle = effects.add(halos);
 
-    for room in 0..TOTAL_ROOMS {
+    for room in 0..tour.room_count() {
         let center = room_center(room);
         let base_pos = center - Vec3::Z * 8.0;
 
@@ -94,7 +96,7 @@ fn spawn_holographic_particles(
 
     info!(
         "✨ Holographic GPU particles spawned for {} rooms",
-        TOTAL_ROOMS
+        tour.room_count()
     );
 }
 
@@ -242,11 +244,12 @@ fn spawn_mesh_fallback(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     quality: Res<QualitySettings>,
+    tour: Res<Tour>,
 ) {
     let mesh = meshes.add(Sphere::new(0.08).mesh().ico(1).unwrap());
     let count = quality.particle_count(8);
 
-    for room in 0..TOTAL_ROOMS {
+    for room in 0..tour.room_count() {
         let center = room_center(room);
 
         for i in 0..count {
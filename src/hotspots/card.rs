@@ -0,0 +1,199 @@
+//! Hotspot card overlay and hover label
+
+use bevy::{prelude::*, ui::Val::*};
+
+use super::{Hotspot, HotspotState};
+use crate::book_reader::BookTheme;
+use crate::tour::Tour;
+
+#[derive(Resource, Default)]
+pub struct HotspotCard {
+    pub open: bool,
+    pub title: String,
+    pub text: String,
+    pub image: Option<Handle<Image>>,
+}
+
+impl HotspotCard {
+    pub fn show(&mut self, title: &str, text: &str, image: Option<Handle<Image>>) {
+        self.open = true;
+        self.title = title.into();
+        self.text = text.into();
+        self.image = image;
+    }
+}
+
+#[derive(Component)]
+pub struct CardPanel;
+
+#[derive(Component)]
+pub struct CardBackdrop;
+
+#[derive(Component)]
+pub struct CardTitle;
+
+#[derive(Component)]
+pub struct CardText;
+
+#[derive(Component)]
+pub struct CardImage;
+
+#[derive(Component)]
+pub struct HotspotLabel;
+
+pub fn setup_hotspot_card(mut commands: Commands, theme: Res<BookTheme>) {
+    commands
+        .spawn((
+            CardPanel,
+            CardBackdrop,
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Percent(100.0),
+                height: Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+            Visibility::Hidden,
+            GlobalZIndex(90),
+        ))
+        .with_children(|p| {
+            p.spawn((
+                Node {
+                    width: Px(520.0),
+                    max_width: Percent(90.0),
+                    max_height: Percent(85.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(12.0),
+                    padding: UiRect::all(Px(24.0)),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                BackgroundColor(theme.bg),
+                BorderRadius::all(Px(16.0)),
+            ))
+            .with_children(|card| {
+                card.spawn((
+                    CardTitle,
+                    Text::new(""),
+                    TextFont::from_font_size(22.0),
+                    TextColor(theme.accent),
+                ));
+                card.spawn((
+                    CardImage,
+                    ImageNode::default(),
+                    Node {
+                        width: Percent(100.0),
+                        display: Display::None,
+                        ..default()
+                    },
+                    BorderRadius::all(Px(8.0)),
+                ));
+                card.spawn((
+                    CardText,
+                    Text::new(""),
+                    TextFont::from_font_size(15.0),
+                    TextColor(theme.text),
+                ));
+                card.spawn((
+                    Text::new("Click or press Esc to close"),
+                    TextFont::from_font_size(11.0),
+                    TextColor(theme.muted),
+                ));
+            });
+        });
+
+    commands.spawn((
+        HotspotLabel,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Percent(38.0),
+            width: Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Visibility::Hidden,
+        GlobalZIndex(80),
+        children![(
+            Text::new(""),
+            TextFont::from_font_size(16.0),
+            TextColor(theme.text),
+            TextShadow::default(),
+        )],
+    ));
+}
+
+/// Escape or a click on the backdrop closes the card (Interact is handled with activation)
+pub fn close_card_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    backdrop: Query<&Interaction, (With<CardBackdrop>, Changed<Interaction>)>,
+    mut card: ResMut<HotspotCard>,
+) {
+    if !card.open {
+        return;
+    }
+    let clicked = backdrop.iter().any(|i| *i == Interaction::Pressed);
+    if keyboard.just_pressed(KeyCode::Escape) || clicked {
+        card.open = false;
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_hotspot_card(
+    card: Res<HotspotCard>,
+    state: Res<HotspotState>,
+    tour: Res<Tour>,
+    hotspots: Query<&Hotspot>,
+    mut panel: Query<&mut Visibility, (With<CardPanel>, Without<HotspotLabel>)>,
+    mut label: Query<(&mut Visibility, &Children), (With<HotspotLabel>, Without<CardPanel>)>,
+    mut title: Query<&mut Text, (With<CardTitle>, Without<CardText>)>,
+    mut text: Query<&mut Text, (With<CardText>, Without<CardTitle>)>,
+    mut image: Query<(&mut ImageNode, &mut Node), With<CardImage>>,
+    mut label_text: Query<&mut Text, (Without<CardTitle>, Without<CardText>)>,
+) {
+    let hovered = state
+        .hovered()
+        .and_then(|e| hotspots.get(e).ok())
+        .and_then(|h| tour.rooms[h.room].hotspots[h.index].label.as_deref())
+        .filter(|_| !card.open);
+    if let Ok((mut vis, children)) = label.single_mut() {
+        vis.set_if_neq(if hovered.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        if let Some(mut t) = children.first().and_then(|c| label_text.get_mut(*c).ok()) {
+            if let Some(hovered) = hovered.filter(|h| t.0 != *h) {
+                t.0 = hovered.into();
+            }
+        }
+    }
+
+    if !card.is_changed() {
+        return;
+    }
+    if let Ok(mut vis) = panel.single_mut() {
+        *vis = if card.open {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if let Ok(mut t) = title.single_mut() {
+        t.0.clone_from(&card.title);
+    }
+    if let Ok(mut t) = text.single_mut() {
+        t.0.clone_from(&card.text);
+    }
+    if let Ok((mut node_image, mut node)) = image.single_mut() {
+        match &card.image {
+            Some(handle) => {
+                node_image.image = handle.clone();
+                node.display = Display::Flex;
+            }
+            None => node.display = Display::None,
+        }
+    }
+}
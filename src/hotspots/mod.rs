@@ -0,0 +1,377 @@
+//! Hotspots - info points pinned to panorama yaw/pitch
+//!
+//! Each room's hotspots are billboards parented to its sky, so they follow the
+//! capture pose and skybox rotation and stream in/out with the room. They are
+//! activated by clicking/tapping (mesh picking), by `InputEvent::Interact`
+//! while gazed at (crosshair / view centre), or by dwelling on them in XR.
+
+mod card;
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::picking::mesh_picking::{MeshPickingCamera, MeshPickingPlugin, MeshPickingSettings};
+use bevy::picking::pointer::PointerButton;
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+use crate::camera::GameCamera;
+use crate::input::{InputEvent, UiWantsPointer};
+use crate::player::PlayerState;
+use crate::portals::TravelToRoom;
+use crate::tour::Tour;
+use crate::world::Skybox;
+use crate::GameState;
+pub use card::HotspotCard;
+use card::{close_card_input, setup_hotspot_card, update_hotspot_card};
+
+/// Billboard radius (m) at the default distance
+const HOTSPOT_RADIUS: f32 = 0.35;
+/// View-centre angle (degrees) within which a hotspot counts as gazed at
+const GAZE_ANGLE: f32 = 4.0;
+/// Picking and Interact can both fire for one tap - ignore repeats within this
+const ACTIVATE_COOLDOWN: f32 = 0.3;
+
+pub struct HotspotsPlugin;
+
+impl Plugin for HotspotsPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<MeshPickingPlugin>() {
+            app.add_plugins(MeshPickingPlugin);
+        }
+        // Only hotspots are ray cast, not every sky and character mesh
+        app.insert_resource(MeshPickingSettings {
+            require_markers: true,
+            ..default()
+        })
+        .init_resource::<HotspotState>()
+        .init_resource::<HotspotSettings>()
+        .init_resource::<HotspotCard>()
+        .add_systems(Startup, setup_hotspot_card)
+        .add_systems(
+            Update,
+            (
+                (mark_picking_cameras, spawn_hotspots),
+                (
+                    track_gaze,
+                    activate_hotspots,
+                    close_card_input,
+                    face_camera,
+                    update_hotspot_card,
+                )
+                    .chain(),
+            )
+                .run_if(in_state(GameState::Viewing)),
+        );
+    }
+}
+
+/// Hotspot entry in the tour manifest
+#[derive(Deserialize, Clone, Debug)]
+pub struct HotspotConfig {
+    /// Degrees from the panorama centre towards its right edge
+    pub yaw: f32,
+    /// Degrees above the horizon
+    pub pitch: f32,
+    /// Shown while the hotspot is hovered or gazed at
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Billboard distance from the sky centre (m)
+    #[serde(default = "default_distance")]
+    pub distance: f32,
+    pub action: HotspotAction,
+}
+
+fn default_distance() -> f32 {
+    10.0
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum HotspotAction {
+    /// Text/image card
+    Card {
+        title: String,
+        #[serde(default)]
+        text: String,
+        #[serde(default)]
+        image: Option<String>,
+    },
+    /// Play an audio clip
+    Audio(String),
+    /// Travel to another room
+    Jump(usize),
+}
+
+impl HotspotConfig {
+    /// Direction in sky-local space, matching the sky sphere's UV layout
+    pub fn direction(&self) -> Vec3 {
+        let (yaw, pitch) = (self.yaw.to_radians(), self.pitch.to_radians());
+        Vec3::new(
+            -pitch.cos() * yaw.cos(),
+            pitch.sin(),
+            pitch.cos() * yaw.sin(),
+        )
+    }
+}
+
+#[derive(Component)]
+pub struct Hotspot {
+    pub room: usize,
+    pub index: usize,
+}
+
+#[derive(Resource)]
+pub struct HotspotSettings {
+    /// Seconds of gaze that activate a hotspot (XR has no pointer to click with)
+    pub gaze_dwell: Option<f32>,
+}
+
+impl Default for HotspotSettings {
+    fn default() -> Self {
+        Self {
+            gaze_dwell: cfg!(any(feature = "vr", feature = "webxr")).then_some(2.0),
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct HotspotState {
+    /// Hotspot under the view centre
+    pub gazed: Option<Entity>,
+    /// Hotspot under the mouse pointer
+    pub pointed: Option<Entity>,
+    /// (room, index) of every hotspot the visitor has activated
+    pub visited: HashSet<(usize, usize)>,
+    dwell: f32,
+    last_activation: f32,
+}
+
+impl HotspotState {
+    pub fn hovered(&self) -> Option<Entity> {
+        self.pointed.or(self.gazed)
+    }
+}
+
+fn mark_picking_cameras(
+    mut cmd: Commands,
+    cameras: Query<Entity, (With<GameCamera>, Without<MeshPickingCamera>)>,
+) {
+    for entity in cameras.iter() {
+        cmd.entity(entity).insert(MeshPickingCamera);
+    }
+}
+
+/// Billboards for a room's hotspots, parented to its (first) sky when it's built
+fn spawn_hotspots(
+    mut cmd: Commands,
+    tour: Res<Tour>,
+    skies: Query<(Entity, &Skybox), Added<Skybox>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+    mut disc: Local<Option<Handle<Mesh>>>,
+) {
+    let disc = disc
+        .get_or_insert_with(|| meshes.add(Circle::new(HOTSPOT_RADIUS)))
+        .clone();
+
+    // Stereo rooms add two skies at once - hotspots go on the first only
+    let mut done = HashSet::new();
+    for (sky_entity, sky) in skies.iter() {
+        if !done.insert(sky.room) {
+            continue;
+        }
+        for (index, hotspot) in tour.rooms[sky.room].hotspots.iter().enumerate() {
+            let scale = hotspot.distance / default_distance();
+            let material = mats.add(StandardMaterial {
+                base_color: Color::srgba(1.0, 0.85, 0.5, 0.85),
+                emissive: LinearRgba::new(1.2, 0.9, 0.4, 1.0),
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                double_sided: true,
+                cull_mode: None,
+                ..default()
+            });
+            let child = cmd
+                .spawn((
+                    Mesh3d(disc.clone()),
+                    MeshMaterial3d(material),
+                    Transform::from_translation(hotspot.direction() * hotspot.distance)
+                        .with_scale(Vec3::splat(scale)),
+                    RenderLayers::layer(sky.room),
+                    Pickable::default(),
+                    Hotspot {
+                        room: sky.room,
+                        index,
+                    },
+                ))
+                .id();
+            cmd.entity(sky_entity).add_child(child);
+        }
+    }
+}
+
+/// Which hotspot sits under the view centre (and the mouse pointer)
+#[allow(clippy::too_many_arguments)]
+fn track_gaze(
+    time: Res<Time>,
+    player: Res<PlayerState>,
+    settings: Res<HotspotSettings>,
+    mut state: ResMut<HotspotState>,
+    mut over: MessageReader<Pointer<Over>>,
+    mut out: MessageReader<Pointer<Out>>,
+    cameras: Query<&GlobalTransform, With<GameCamera>>,
+    hotspots: Query<(Entity, &Hotspot, &GlobalTransform)>,
+) {
+    for event in over.read() {
+        if hotspots.contains(event.entity) {
+            state.pointed = Some(event.entity);
+        }
+    }
+    for event in out.read() {
+        if state.pointed == Some(event.entity) {
+            state.pointed = None;
+        }
+    }
+    if state.pointed.is_some_and(|e| !hotspots.contains(e)) {
+        state.pointed = None;
+    }
+
+    let Ok(cam) = cameras.single() else {
+        return;
+    };
+    let forward = cam.forward().as_vec3();
+    let gazed = hotspots
+        .iter()
+        .filter(|(_, hotspot, _)| hotspot.room == player.room)
+        .map(|(entity, _, tr)| {
+            let dir = (tr.translation() - cam.translation()).normalize_or_zero();
+            (entity, dir.angle_between(forward).to_degrees())
+        })
+        .filter(|(_, angle)| *angle < GAZE_ANGLE)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
+
+    if gazed != state.gazed {
+        state.gazed = gazed;
+        state.dwell = 0.0;
+    } else if gazed.is_some() && settings.gaze_dwell.is_some() {
+        state.dwell += time.delta_secs();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn activate_hotspots(
+    time: Res<Time>,
+    tour: Res<Tour>,
+    settings: Res<HotspotSettings>,
+    mut state: ResMut<HotspotState>,
+    mut card: ResMut<HotspotCard>,
+    mut input: MessageReader<InputEvent>,
+    mut clicks: MessageReader<Pointer<Click>>,
+    mut travel: MessageWriter<TravelToRoom>,
+    ui_wants: Res<UiWantsPointer>,
+    hotspots: Query<&Hotspot>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+) {
+    let interact = input
+        .read()
+        .any(|event| matches!(event, InputEvent::Interact));
+    // Clicks on the card land on whatever hotspot is behind it
+    if card.open {
+        clicks.clear();
+        if interact {
+            card.open = false;
+        }
+        return;
+    }
+
+    // Clicks on UI panels land on whatever hotspot is behind them
+    if ui_wants.0 {
+        clicks.clear();
+    }
+    let mut target = clicks
+        .read()
+        .filter(|click| click.event.button == PointerButton::Primary)
+        .find_map(|click| hotspots.contains(click.entity).then_some(click.entity));
+    if interact {
+        target = target.or(state.gazed);
+    }
+    if let Some(dwell) = settings.gaze_dwell {
+        if state.dwell >= dwell {
+            state.dwell = f32::MIN; // once per gaze
+            target = target.or(state.gazed);
+        }
+    }
+
+    let now = time.elapsed_secs();
+    let Some(hotspot) = target.and_then(|e| hotspots.get(e).ok()) else {
+        return;
+    };
+    if now - state.last_activation < ACTIVATE_COOLDOWN {
+        return;
+    }
+    state.last_activation = now;
+    state.visited.insert((hotspot.room, hotspot.index));
+
+    let config = &tour.rooms[hotspot.room].hotspots[hotspot.index];
+    match &config.action {
+        HotspotAction::Card { title, text, image } => {
+            card.show(
+                title,
+                text,
+                image.as_ref().map(|path| asset_server.load(path)),
+            );
+            info!("📌 Hotspot card: {}", title);
+        }
+        HotspotAction::Audio(path) => {
+            audio.play(asset_server.load(path));
+            info!("📌 Hotspot audio: {}", path);
+        }
+        HotspotAction::Jump(room) => {
+            travel.write(TravelToRoom { room: *room });
+            info!("📌 Hotspot jump → {}", tour.rooms[*room].name);
+        }
+    }
+}
+
+/// Billboards face the camera; hovered ones grow and brighten
+fn face_camera(
+    time: Res<Time>,
+    state: Res<HotspotState>,
+    cameras: Query<&GlobalTransform, With<GameCamera>>,
+    mut hotspots: Query<(
+        Entity,
+        &Hotspot,
+        &mut Transform,
+        &ChildOf,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
+    parents: Query<&GlobalTransform>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+    tour: Res<Tour>,
+) {
+    let Ok(cam) = cameras.single() else {
+        return;
+    };
+    let pulse = 1.0 + (time.elapsed_secs() * 3.0).sin() * 0.06;
+
+    for (entity, hotspot, mut tr, child_of, mat) in hotspots.iter_mut() {
+        let Ok(parent) = parents.get(child_of.parent()) else {
+            continue;
+        };
+        let config = &tour.rooms[hotspot.room].hotspots[hotspot.index];
+        let parent_rot = parent.compute_transform().rotation;
+        let world_pos = parent.transform_point(tr.translation);
+        let look = Transform::from_translation(world_pos).looking_at(cam.translation(), Vec3::Y);
+        tr.rotation = parent_rot.inverse() * look.rotation;
+
+        let hovered = state.hovered() == Some(entity);
+        let base = config.distance / default_distance();
+        tr.scale = Vec3::splat(base * if hovered { 1.4 } else { pulse });
+        if let Some(mat) = mats.get_mut(&mat.0) {
+            let glow = if hovered { 2.5 } else { 1.2 };
+            mat.emissive = LinearRgba::new(glow, glow * 0.75, glow * 0.35, 1.0);
+        }
+    }
+}
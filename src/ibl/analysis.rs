@@ -3,16 +3,40 @@
 use super::{
     AnalyzePanoramaEvent, IblDirectionalLight, IblLightProbe, IblReadyEvent, SphericalHarmonics,
 };
-use crate::loading::PanoramaAssets;
+use crate::room_streaming::RoomStreaming;
+use crate::tour::Tour;
+use crate::world::{exposure_scale, is_hdr_format, variant_blend, Eye, WorldClock};
 use async_channel::{bounded, Receiver, Sender};
 use bevy::prelude::*;
 use std::f32::consts::PI;
 
 #[derive(Resource)]
 pub struct IblAnalysisChannel {
-    pub rx: Receiver<IblAnalysisResult>,
+    pub rx: Receiver<(usize, IblAnalysisResult)>,
 }
 
+/// Analysis of each time-of-day panorama, blended by the world clock
+/// (a single entry for rooms without variants)
+#[derive(Resource)]
+pub struct IblVariants {
+    pub hours: Vec<f32>,
+    pub results: Vec<Option<IblAnalysisResult>>,
+    shown: Option<(usize, usize, f32)>,
+}
+
+impl IblVariants {
+    /// Results blended for `hour`, once every variant is analyzed
+    fn blend(&self, hour: f32) -> Option<((usize, usize, f32), IblAnalysisResult)> {
+        if self.results.iter().any(Option::is_none) {
+            return None;
+        }
+        let (a, b, t) = variant_blend(&self.hours, hour).unwrap_or((0, 0, 0.0));
+        let (from, to) = (self.results[a].as_ref()?, self.results[b].as_ref()?);
+        Some(((a, b, t), from.lerp(to, t)))
+    }
+}
+
+#[derive(Clone)]
 pub struct IblAnalysisResult {
     pub dominant_dir: Vec3,
     pub dominant_color: Color,
@@ -24,10 +48,47 @@ pub struct IblAnalysisResult {
     pub contrast: f32,
 }
 
+impl IblAnalysisResult {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: Color, b: Color| a.mix(&b, t);
+        let mut sh = self.sh.clone();
+        for (coeff, other) in sh.coeffs.iter_mut().zip(&other.sh.coeffs) {
+            for (c, o) in coeff.iter_mut().zip(other) {
+                *c = c.lerp(*o, t);
+            }
+        }
+        Self {
+            dominant_dir: self
+                .dominant_dir
+                .lerp(other.dominant_dir, t)
+                .normalize_or(other.dominant_dir),
+            dominant_color: mix(self.dominant_color, other.dominant_color),
+            dominant_intensity: self.dominant_intensity.lerp(other.dominant_intensity, t),
+            ambient_color: mix(self.ambient_color, other.ambient_color),
+            ambient_intensity: self.ambient_intensity.lerp(other.ambient_intensity, t),
+            sh,
+            exposure: self.exposure.lerp(other.exposure, t),
+            contrast: self.contrast.lerp(other.contrast, t),
+        }
+    }
+
+    fn apply(self, light_probe: &mut IblLightProbe) {
+        light_probe.dominant_light_dir = self.dominant_dir;
+        light_probe.dominant_light_color = self.dominant_color;
+        light_probe.dominant_light_intensity = self.dominant_intensity;
+        light_probe.ambient_color = self.ambient_color;
+        light_probe.ambient_intensity = self.ambient_intensity;
+        light_probe.spherical_harmonics = self.sh;
+        light_probe.exposure = self.exposure;
+        light_probe.contrast = self.contrast;
+    }
+}
+
 pub fn analyze_panorama_system(
     mut events: MessageReader<AnalyzePanoramaEvent>,
     mut light_probe: ResMut<IblLightProbe>,
-    panorama_assets: Res<PanoramaAssets>,
+    tour: Res<Tour>,
+    streaming: Res<RoomStreaming>,
     images: Res<Assets<Image>>,
     mut commands: Commands,
 ) {
@@ -36,53 +97,114 @@ pub fn analyze_panorama_system(
             continue;
         }
 
-        let Some(image) = images.get(&panorama_assets.demo_panorama) else {
+        let room = &tour.rooms[0];
+        let Some(assets) = streaming.assets(0) else {
+            continue;
+        };
+        // Time-of-day rooms are analyzed per variant and blended by the clock
+        let (hours, panoramas): (Vec<f32>, Vec<&Handle<Image>>) = if room.times.is_empty() {
+            (vec![0.0], vec![&assets.panorama])
+        } else {
+            room.times
+                .iter()
+                .map(|v| v.hour)
+                .zip(&assets.variants)
+                .unzip()
+        };
+        let Some(sources) = panoramas
+            .iter()
+            .map(|handle| images.get(*handle).filter(|image| image.data.is_some()))
+            .collect::<Option<Vec<_>>>()
+        else {
             continue;
         };
-        let Some(data) = &image.data else { continue };
 
-        let (tx, rx): (Sender<IblAnalysisResult>, Receiver<IblAnalysisResult>) = bounded(1);
+        let count = sources.len();
+        let (tx, rx): (
+            Sender<(usize, IblAnalysisResult)>,
+            Receiver<(usize, IblAnalysisResult)>,
+        ) = bounded(count);
         commands.insert_resource(IblAnalysisChannel { rx });
+        commands.insert_resource(IblVariants {
+            hours,
+            results: vec![None; count],
+            shown: None,
+        });
 
-        let width = image.width() as usize;
-        let height = image.height() as usize;
-        let bpp = data.len() / (width * height);
-        let data_clone = data.clone();
+        let sources: Vec<Image> = sources.into_iter().cloned().collect();
+        let exposure = exposure_scale(room.exposure);
+        let region = room.stereo.eye_region(Eye::Left);
 
         // Spawn async analysis task
         #[cfg(not(target_arch = "wasm32"))]
         std::thread::spawn(move || {
-            let result = analyze_image(&data_clone, width, height, bpp);
-            let _ = tx.send_blocking(result);
+            for (i, image) in sources.iter().enumerate() {
+                let result = analyze_image(image, exposure, region);
+                let _ = tx.send_blocking((i, result));
+            }
         });
 
+        // No threads on the web: analyze_next_variant works through them a frame at a time
         #[cfg(target_arch = "wasm32")]
-        {
-            let result = analyze_image(&data_clone, width, height, bpp);
-            let _ = tx.try_send(result);
-        }
+        commands.insert_resource(IblPendingVariants {
+            sources,
+            next: 0,
+            exposure,
+            region,
+            tx,
+        });
 
-        light_probe.env_map = Some(panorama_assets.demo_panorama.clone());
+        light_probe.env_map = Some(panoramas[0].clone());
         info!("🔄 IBL analysis started async...");
     }
 }
 
+/// Variants still waiting for analysis on the web
+#[cfg(target_arch = "wasm32")]
+#[derive(Resource)]
+pub struct IblPendingVariants {
+    sources: Vec<Image>,
+    next: usize,
+    exposure: f32,
+    region: Rect,
+    tx: Sender<(usize, IblAnalysisResult)>,
+}
+
+/// Analyze one pending variant per frame so the page doesn't stall
+#[cfg(target_arch = "wasm32")]
+pub fn analyze_next_variant(pending: Option<ResMut<IblPendingVariants>>, mut commands: Commands) {
+    let Some(mut pending) = pending else { return };
+    let i = pending.next;
+    if let Some(image) = pending.sources.get(i) {
+        let result = analyze_image(image, pending.exposure, pending.region);
+        let _ = pending.tx.try_send((i, result));
+        pending.next += 1;
+    }
+    if pending.next >= pending.sources.len() {
+        commands.remove_resource::<IblPendingVariants>();
+    }
+}
+
 pub fn receive_analysis_results(
     channel: Option<Res<IblAnalysisChannel>>,
+    variants: Option<ResMut<IblVariants>>,
+    clock: Res<WorldClock>,
     mut light_probe: ResMut<IblLightProbe>,
     mut ready_events: MessageWriter<IblReadyEvent>,
 ) {
-    let Some(channel) = channel else { return };
-
-    if let Ok(result) = channel.rx.try_recv() {
-        light_probe.dominant_light_dir = result.dominant_dir;
-        light_probe.dominant_light_color = result.dominant_color;
-        light_probe.dominant_light_intensity = result.dominant_intensity;
-        light_probe.ambient_color = result.ambient_color;
-        light_probe.ambient_intensity = result.ambient_intensity;
-        light_probe.spherical_harmonics = result.sh;
-        light_probe.exposure = result.exposure;
-        light_probe.contrast = result.contrast;
+    let (Some(channel), Some(mut variants)) = (channel, variants) else {
+        return;
+    };
+    if light_probe.analyzed {
+        return;
+    }
+
+    while let Ok((i, result)) = channel.rx.try_recv() {
+        variants.results[i] = Some(result);
+    }
+    if let Some((shown, result)) = variants.blend(clock.hour) {
+        variants.shown = Some(shown);
+        result.apply(&mut light_probe);
         light_probe.analyzed = true;
 
         ready_events.write(IblReadyEvent);
@@ -95,8 +217,63 @@ pub fn receive_analysis_results(
     }
 }
 
-fn analyze_image(data: &[u8], width: usize, height: usize, bpp: usize) -> IblAnalysisResult {
-    let step = (width / 64).max(1);
+/// Follow the world clock between time-of-day analyses
+pub fn blend_ibl_variants(
+    clock: Res<WorldClock>,
+    variants: Option<ResMut<IblVariants>>,
+    mut light_probe: ResMut<IblLightProbe>,
+) {
+    let Some(mut variants) = variants else { return };
+    if !light_probe.analyzed || variants.hours.len() < 2 {
+        return;
+    }
+    let Some(((a, b, t), result)) = variants.blend(clock.hour) else {
+        return;
+    };
+    // Re-light only on visible steps
+    let settled = variants
+        .shown
+        .is_some_and(|(sa, sb, st)| (sa, sb) == (a, b) && (st - t).abs() < 0.01);
+    if !settled {
+        variants.shown = Some((a, b, t));
+        result.apply(&mut light_probe);
+    }
+}
+
+/// Illuminance (lux) of unit HDR radiance over one steradian. At Bevy's default
+/// camera exposure (EV100 9.7) this makes lit white surfaces match the sky.
+const LUX_PER_UNIT_RADIANCE: f32 = 1000.0;
+
+/// Samples above this fraction of the peak luminance count towards the sun
+const SUN_THRESHOLD: f32 = 0.5;
+
+/// Analyze the equirect view inside `region` (normalized; one eye of a stereo pair)
+fn analyze_image(image: &Image, exposure: f32, region: Rect) -> IblAnalysisResult {
+    let size = image.size().as_vec2();
+    let origin = (region.min * size).as_uvec2();
+    let width = (region.width() * size.x) as usize;
+    let height = (region.height() * size.y) as usize;
+
+    // 8-bit panoramas are display-referred; float formats carry scene radiance
+    let hdr = is_hdr_format(image.texture_descriptor.format);
+    let step = if hdr {
+        (width / 1024).max(1)
+    } else {
+        (width / 64).max(1)
+    };
+
+    let texel = |x: usize, y: usize| -> Option<Vec3> {
+        let color = image
+            .get_color_at(origin.x + x as u32, origin.y + y as u32)
+            .ok()?;
+        Some(if hdr {
+            let c = color.to_linear();
+            Vec3::new(c.red, c.green, c.blue) * exposure
+        } else {
+            let c = color.to_srgba();
+            Vec3::new(c.red, c.green, c.blue)
+        })
+    };
 
     let mut brightest = 0.0f32;
     let mut brightest_dir = Vec3::Y;
@@ -109,40 +286,31 @@ fn analyze_image(data: &[u8], width: usize, height: usize, bpp: usize) -> IblAna
 
     for y in (0..height).step_by(step) {
         for x in (0..width).step_by(step) {
-            let idx = (y * width + x) * bpp;
-            if idx + 2 >= data.len() {
-                continue;
-            }
-
-            let r = data[idx] as f32 / 255.0;
-            let g = data[idx + 1] as f32 / 255.0;
-            let b = data[idx + 2] as f32 / 255.0;
+            let Some(rgb) = texel(x, y) else { continue };
+            let (r, g, b) = (rgb.x, rgb.y, rgb.z);
             let lum = 0.299 * r + 0.587 * g + 0.114 * b;
 
             lum_sum += lum;
             count += 1;
 
-            // Equirectangular to direction
-            let u = x as f32 / width as f32;
-            let v = y as f32 / height as f32;
-            let theta = u * 2.0 * PI;
-            let phi = v * PI;
-            let dir = Vec3::new(phi.sin() * theta.sin(), phi.cos(), phi.sin() * theta.cos());
-
-            // Solid angle weight for equirectangular
-            let solid_angle = phi.sin().max(0.001);
+            let (dir, solid_angle) = equirect_dir(x, y, width, height);
 
             // Accumulate SH coefficients
-            accumulate_sh(&mut sh, dir, Vec3::new(r, g, b), solid_angle);
+            accumulate_sh(&mut sh, dir, rgb, solid_angle);
 
             if lum > brightest {
                 brightest = lum;
                 brightest_dir = dir;
-                brightest_color = Color::srgb(r, g, b);
+                brightest_color = if hdr {
+                    let peak = rgb / rgb.max_element().max(1e-6);
+                    Color::linear_rgb(peak.x, peak.y, peak.z)
+                } else {
+                    Color::srgb(r, g, b)
+                };
             }
 
             let w = (1.0 - lum * 0.5).max(0.1) * solid_angle;
-            total_color += Vec3::new(r, g, b) * w;
+            total_color += rgb * w;
             total_weight += w;
         }
     }
@@ -159,18 +327,40 @@ fn analyze_image(data: &[u8], width: usize, height: usize, bpp: usize) -> IblAna
     let ambient = total_color / total_weight.max(1.0);
     let contrast = brightest / avg_lum.max(0.01);
 
-    let intensity = if contrast > 3.0 {
-        50000.0 + brightest * 50000.0
+    let (dominant_dir, intensity) = if hdr {
+        // Integrate the sun's radiance over its solid angle
+        let cell = (step * step) as f32 * (2.0 * PI / width as f32) * (PI / height as f32);
+        let mut sun_dir = Vec3::ZERO;
+        let mut irradiance = 0.0f32;
+        for y in (0..height).step_by(step) {
+            for x in (0..width).step_by(step) {
+                let Some(rgb) = texel(x, y) else { continue };
+                let lum = 0.299 * rgb.x + 0.587 * rgb.y + 0.114 * rgb.z;
+                if lum < brightest * SUN_THRESHOLD {
+                    continue;
+                }
+                let (dir, sin_phi) = equirect_dir(x, y, width, height);
+                let flux = lum * sin_phi * cell;
+                sun_dir += dir * flux;
+                irradiance += flux;
+            }
+        }
+        (
+            sun_dir.normalize_or(brightest_dir),
+            irradiance * LUX_PER_UNIT_RADIANCE,
+        )
+    } else if contrast > 3.0 {
+        (brightest_dir, 50000.0 + brightest * 50000.0)
     } else if contrast > 1.5 {
-        10000.0 + brightest * 40000.0
+        (brightest_dir, 10000.0 + brightest * 40000.0)
     } else {
-        1000.0 + brightest * 9000.0
+        (brightest_dir, 1000.0 + brightest * 9000.0)
     };
 
     let exposure = (0.5 / avg_lum.max(0.01)).clamp(0.5, 3.0);
 
     IblAnalysisResult {
-        dominant_dir: brightest_dir.normalize(),
+        dominant_dir: dominant_dir.normalize(),
         dominant_color: brightest_color,
         dominant_intensity: intensity,
         ambient_color: Color::srgb(
@@ -185,6 +375,16 @@ fn analyze_image(data: &[u8], width: usize, height: usize, bpp: usize) -> IblAna
     }
 }
 
+/// Direction and solid-angle weight (sin φ) of an equirect pixel
+fn equirect_dir(x: usize, y: usize, width: usize, height: usize) -> (Vec3, f32) {
+    let u = x as f32 / width as f32;
+    let v = y as f32 / height as f32;
+    let theta = u * 2.0 * PI;
+    let phi = v * PI;
+    let dir = Vec3::new(phi.sin() * theta.sin(), phi.cos(), phi.sin() * theta.cos());
+    (dir, phi.sin().max(0.001))
+}
+
 fn accumulate_sh(sh: &mut SphericalHarmonics, dir: Vec3, color: Vec3, weight: f32) {
     let d = dir.normalize();
     let (x, y, z) = (d.x, d.y, d.z);
@@ -212,27 +412,31 @@ fn accumulate_sh(sh: &mut SphericalHarmonics, dir: Vec3, color: Vec3, weight: f3
 pub fn apply_ibl_lighting_system(
     mut commands: Commands,
     light_probe: Res<IblLightProbe>,
-    existing: Query<Entity, With<IblDirectionalLight>>,
+    mut existing: Query<(&mut DirectionalLight, &mut Transform), With<IblDirectionalLight>>,
 ) {
     if !light_probe.analyzed || !light_probe.is_changed() {
         return;
     }
 
-    for e in existing.iter() {
-        commands.entity(e).despawn();
+    // Updated in place - the clock re-blends the probe as time passes
+    let transform = Transform::default().looking_to(-light_probe.dominant_light_dir, Vec3::Y);
+    if let Ok((mut light, mut tr)) = existing.single_mut() {
+        light.color = light_probe.dominant_light_color;
+        light.illuminance = light_probe.dominant_light_intensity;
+        *tr = transform;
+    } else {
+        commands.spawn((
+            DirectionalLight {
+                color: light_probe.dominant_light_color,
+                illuminance: light_probe.dominant_light_intensity,
+                shadows_enabled: true,
+                ..default()
+            },
+            transform,
+            IblDirectionalLight,
+        ));
     }
 
-    commands.spawn((
-        DirectionalLight {
-            color: light_probe.dominant_light_color,
-            illuminance: light_probe.dominant_light_intensity,
-            shadows_enabled: true,
-            ..default()
-        },
-        Transform::default().looking_to(-light_probe.dominant_light_dir, Vec3::Y),
-        IblDirectionalLight,
-    ));
-
     // Sample SH for ambient from multiple directions
     let up_color = light_probe.spherical_harmonics.sample(Vec3::Y);
     let ambient = Color::srgb(
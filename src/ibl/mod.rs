@@ -25,12 +25,22 @@ impl Plugin for IblPlugin {
                 (
                     analyze_panorama_system,
                     receive_analysis_results,
+                    blend_ibl_variants,
                     apply_ibl_lighting_system,
                     apply_ibl_to_models,
                 )
                     .chain()
                     .run_if(in_state(GameState::Viewing)),
             );
+
+        #[cfg(target_arch = "wasm32")]
+        app.add_systems(
+            Update,
+            analyze_next_variant
+                .after(analyze_panorama_system)
+                .before(receive_analysis_results)
+                .run_if(in_state(GameState::Viewing)),
+        );
     }
 }
 
@@ -38,16 +48,19 @@ fn trigger_analysis(mut events: MessageWriter<AnalyzePanoramaEvent>) {
     events.write(AnalyzePanoramaEvent);
 }
 
-/// Apply IBL-aware materials to marked models
+/// Apply IBL-aware materials to marked models (and to models streamed in later)
 fn apply_ibl_to_models(
     mut events: MessageReader<IblReadyEvent>,
     light_probe: Res<IblLightProbe>,
     models: Query<Entity, With<IblLitModel>>,
+    added: Query<Entity, Added<IblLitModel>>,
     children: Query<&Children>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mat_handles: Query<&MeshMaterial3d<StandardMaterial>>,
 ) {
+    let mut ready = false;
     for _ in events.read() {
+        ready = true;
         for entity in models.iter() {
             apply_ibl_recursive(
                 entity,
@@ -62,6 +75,19 @@ fn apply_ibl_to_models(
             models.iter().count()
         );
     }
+
+    // The ready pass above already covered models added this frame
+    if light_probe.analyzed && !ready {
+        for entity in added.iter() {
+            apply_ibl_recursive(
+                entity,
+                &light_probe,
+                &children,
+                &mut materials,
+                &mat_handles,
+            );
+        }
+    }
 }
 
 fn apply_ibl_recursive(
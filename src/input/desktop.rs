@@ -1,6 +1,5 @@
 //! Desktop input - WASD + mouse look + gamepad
 
-use bevy::input::gamepad::GamepadButton;
 use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
@@ -32,6 +31,25 @@ pub struct CursorGrabDelay {
 
 const GRAB_DELAY_FRAMES: u32 = 3;
 
+/// Stick travel ignored around the centre
+const STICK_DEADZONE: f32 = 0.15;
+/// Look response exponent - fine aim near the centre, fast turns at full tilt
+const LOOK_CURVE: f32 = 2.0;
+/// Right stick at full tilt, in mouse pixels per second
+const LOOK_SPEED: f32 = 900.0;
+/// FOV change (degrees per second) with a trigger fully pulled
+const TRIGGER_FOV_SPEED: f32 = 40.0;
+
+/// Radial deadzone, rescaled so output starts at 0 at its edge, then curved
+fn shape_stick(stick: Vec2, curve: f32) -> Vec2 {
+    let len = stick.length();
+    if len <= STICK_DEADZONE {
+        return Vec2::ZERO;
+    }
+    let t = ((len - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).min(1.0);
+    stick / len * t.powf(curve)
+}
+
 fn update_grab_delay(mut delay: ResMut<CursorGrabDelay>) {
     delay.frames_since_viewing = delay.frames_since_viewing.saturating_add(1);
 }
@@ -67,9 +85,10 @@ fn handle_cursor_grab(
 }
 
 fn read_desktop_input(
+    time: Res<Time>,
     keys: Res<ButtonInput<KeyCode>>,
     mouse: Res<ButtonInput<MouseButton>>,
-    _gamepad_buttons: Option<Res<ButtonInput<GamepadButton>>>,
+    gamepads: Query<&Gamepad>,
     mouse_motion: Res<AccumulatedMouseMotion>,
     mut state: ResMut<InputState>,
     mut events: MessageWriter<InputEvent>,
@@ -89,21 +108,37 @@ fn read_desktop_input(
         movement.x += 1.0;
     }
 
+    // Left stick when the keys are idle (stick up = forward = -y), keeping
+    // its tilt so a half push walks slower
+    let stick = gamepads
+        .iter()
+        .map(|pad| shape_stick(pad.left_stick(), 1.0))
+        .fold(Vec2::ZERO, |a, b| a + b)
+        .clamp_length_max(1.0);
+
     state.movement = if movement != Vec2::ZERO {
         movement.normalize()
     } else {
-        Vec2::ZERO
+        Vec2::new(stick.x, -stick.y)
     };
     if state.movement != Vec2::ZERO {
         events.write(InputEvent::Move(state.movement));
     }
 
-    // Look - only when cursor is locked
-    if state.cursor_locked && mouse_motion.delta != Vec2::ZERO {
-        state.look_delta = mouse_motion.delta;
-        events.write(InputEvent::Look(mouse_motion.delta));
+    // Look - mouse only when cursor is locked, right stick always (stick up = look up)
+    let dt = time.delta_secs();
+    let mut look = if state.cursor_locked {
+        mouse_motion.delta
     } else {
-        state.look_delta = Vec2::ZERO;
+        Vec2::ZERO
+    };
+    for pad in gamepads.iter() {
+        let stick = shape_stick(pad.right_stick(), LOOK_CURVE);
+        look += Vec2::new(stick.x, -stick.y) * LOOK_SPEED * dt;
+    }
+    state.look_delta = look;
+    if look != Vec2::ZERO {
+        events.write(InputEvent::Look(look));
     }
 
     // Actions
@@ -122,4 +157,19 @@ fn read_desktop_input(
     if keys.just_pressed(KeyCode::Escape) {
         events.write(InputEvent::ToggleMenu);
     }
+
+    // Gamepad actions: A interact, Y next room, triggers zoom (B closes the book)
+    for pad in gamepads.iter() {
+        if pad.just_pressed(GamepadButton::South) {
+            events.write(InputEvent::Interact);
+        }
+        if pad.just_pressed(GamepadButton::North) {
+            events.write(InputEvent::NextRoom);
+        }
+        let zoom = pad.get(GamepadButton::LeftTrigger2).unwrap_or(0.0)
+            - pad.get(GamepadButton::RightTrigger2).unwrap_or(0.0);
+        if zoom.abs() > STICK_DEADZONE {
+            events.write(InputEvent::AdjustFov(zoom * TRIGGER_FOV_SPEED * dt));
+        }
+    }
 }
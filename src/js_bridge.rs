@@ -1,4 +1,4 @@
-//! JS Bridge - WASM ↔ HTML communication for loading state
+//! JS Bridge - WASM ↔ HTML communication for loading state and announcements
 
 use bevy::prelude::*;
 
@@ -23,10 +23,24 @@ export function update_loading_progress(msg) {
     const progress = document.getElementById('progress');
     if (progress) progress.textContent = msg;
 }
+
+export function announce(msg) {
+    let region = document.getElementById('announcer');
+    if (!region) {
+        region = document.createElement('div');
+        region.id = 'announcer';
+        region.setAttribute('aria-live', 'polite');
+        region.style.cssText = 'position:absolute;width:1px;height:1px;overflow:hidden;clip-path:inset(50%);white-space:nowrap';
+        document.body.appendChild(region);
+    }
+    region.textContent = msg;
+}
 ")]
 extern "C" {
     pub fn hide_loading_overlay();
     pub fn update_loading_progress(msg: &str);
+    /// Read `msg` out through a hidden `aria-live` region (empty clears it)
+    pub fn announce(msg: &str);
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -35,6 +49,10 @@ pub fn hide_loading_overlay() {}
 #[cfg(not(target_arch = "wasm32"))]
 pub fn update_loading_progress(_msg: &str) {}
 
+/// Native builds reach screen readers through AccessKit instead
+#[cfg(not(target_arch = "wasm32"))]
+pub fn announce(_msg: &str) {}
+
 /// Resource to track if we've signaled ready to JS
 #[derive(Resource, Default)]
 pub struct JsBridgeState {
@@ -9,20 +9,27 @@ mod core;
 mod energy_particles;
 mod glb_character;
 mod holographic;
+mod hotspots;
 mod ibl;
 mod input;
 mod js_bridge;
 mod loading;
+mod locale;
+mod minimap;
 mod panorama;
 #[cfg(feature = "particles")]
 mod particles;
 mod performance;
 mod platform;
 mod player;
+mod portal_transition;
 mod portals;
 mod post_process;
 mod room_audio;
+mod room_streaming;
+mod room_video;
 mod routes;
+mod tour;
 mod upload_room;
 mod vortex_transition;
 mod world;
@@ -40,9 +47,11 @@ pub use core::{CorePlugin, DesktopOnly, Os, PlatformEntity, VrOnly};
 pub use energy_particles::EnergyParticlesPlugin;
 pub use glb_character::GlbCharacterPlugin;
 pub use holographic::HolographicParticlesPlugin;
+pub use hotspots::HotspotsPlugin;
 pub use ibl::IblPlugin;
 pub use input::{InputEvent, InputPlugin, InputState, UiWantsPointer};
 pub use loading::LoadingPlugin;
+pub use minimap::MinimapPlugin;
 pub use panorama::PanoramaPlugin;
 #[cfg(feature = "particles")]
 pub use particles::GpuParticlesPlugin;
@@ -52,10 +61,14 @@ pub use performance::{
 };
 pub use platform::{on_desktop, on_vr, on_webxr, Platform, PlatformPlugin, SwitchPlatform};
 pub use player::PlayerPlugin;
+pub use portal_transition::PortalTransitionPlugin;
 pub use portals::PortalsPlugin;
 pub use post_process::PostProcessPlugin;
 pub use room_audio::RoomAudioPlugin;
+pub use room_streaming::RoomStreamingPlugin;
+pub use room_video::RoomVideoPlugin;
 pub use routes::{get_app_mode, AppMode};
+pub use tour::{Tour, TourPlugin};
 pub use upload_room::UploadRoomPlugin;
 pub use vortex_transition::VortexTransitionPlugin;
 pub use world::WorldPlugin;
@@ -75,6 +88,8 @@ pub use platform::WebXrPlatformPlugin;
 pub enum GameState {
     #[default]
     Loading,
+    /// Tour manifest loaded, waiting for the first room's textures and models
+    StartingRoom,
     Viewing,
 }
 
@@ -99,6 +114,7 @@ impl Plugin for GameCorePlugin {
         match mode {
             AppMode::FullExperience => {
                 app.add_plugins((
+                    TourPlugin,
                     LoadingPlugin,
                     PanoramaPlugin,
                     CharacterPlugin,
@@ -110,10 +126,20 @@ impl Plugin for GameCorePlugin {
                     GlbCharacterPlugin,
                     HolographicParticlesPlugin,
                     RoomAudioPlugin,
+                    RoomVideoPlugin,
                     BookReaderPlugin,
                     PostProcessPlugin,
                 ));
 
+                // Per-room texture and model streaming, panorama hotspots, minimap,
+                // portal crossing overlay
+                app.add_plugins((
+                    RoomStreamingPlugin,
+                    HotspotsPlugin,
+                    MinimapPlugin,
+                    PortalTransitionPlugin,
+                ));
+
                 // GPU particles (desktop only)
                 #[cfg(feature = "particles")]
                 app.add_plugins((EnergyParticlesPlugin, GpuParticlesPlugin));
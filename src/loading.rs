@@ -1,7 +1,8 @@
 use crate::js_bridge::{hide_loading_overlay, update_loading_progress, JsBridgeState};
+use crate::tour::{Tour, TourManifest};
 use crate::GameState;
 use bevy::animation::AnimationPlayer;
-use bevy::gltf::{Gltf, GltfExtras, GltfMaterialExtras, GltfMeshExtras, GltfSceneExtras};
+use bevy::gltf::{GltfExtras, GltfMaterialExtras, GltfMeshExtras, GltfSceneExtras};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
@@ -18,9 +19,9 @@ impl Plugin for LoadingPlugin {
             .register_type::<AnimationTransitions>()
             .add_loading_state(
                 LoadingState::new(GameState::Loading)
-                    .continue_to_state(GameState::Viewing)
-                    .load_collection::<PanoramaAssets>()
-                    .load_collection::<ModelAssets>(),
+                    .continue_to_state(GameState::StartingRoom)
+                    .load_collection::<TourAssets>()
+                    .finally_init_resource::<Tour>(),
             )
             .add_systems(OnEnter(GameState::Loading), on_enter_loading)
             .add_systems(OnEnter(GameState::Viewing), on_enter_viewing);
@@ -39,26 +40,9 @@ fn on_enter_viewing(mut bridge: ResMut<JsBridgeState>) {
     }
 }
 
+/// Tour manifest - room audio loads as its dependencies, the rest is streamed per room
 #[derive(AssetCollection, Resource)]
-pub struct PanoramaAssets {
-    #[asset(path = "panoramas/demo.jpg")]
-    pub demo_panorama: Handle<Image>,
-
-    #[asset(path = "panoramas/demo2.jpg")]
-    pub demo2_panorama: Handle<Image>,
-
-    #[asset(path = "panoramas/demo3.jpg")]
-    pub demo3_panorama: Handle<Image>,
-}
-
-#[derive(AssetCollection, Resource)]
-pub struct ModelAssets {
-    #[asset(path = "models/modelo1.glb")]
-    pub modelo1: Handle<Gltf>,
-
-    #[asset(path = "models/modelo2.glb")]
-    pub modelo2: Handle<Gltf>,
-
-    #[asset(path = "models/modelo3.glb")]
-    pub modelo3: Handle<Gltf>,
+pub struct TourAssets {
+    #[asset(path = "tours/default.tour.ron")]
+    pub manifest: Handle<TourManifest>,
 }
@@ -0,0 +1,82 @@
+//! Locale - visitor language and translatable manifest strings
+//!
+//! Manifest text can be a plain string or a map of language tags to
+//! translations. The language comes from the tour's `locale`, or else the
+//! browser / system language.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Language used when neither the tour nor the system names one
+const FALLBACK_LANGUAGE: &str = "en";
+
+/// Visitor language as a BCP 47 tag (`pt-BR`, `en`, ...)
+#[derive(Resource, Clone, Debug)]
+pub struct Locale(pub String);
+
+impl Locale {
+    /// Tour override, else the browser / system language
+    pub fn detect(tour_locale: Option<&str>) -> Self {
+        let tag = tour_locale
+            .map(str::to_owned)
+            .or_else(system_language)
+            .unwrap_or_else(|| FALLBACK_LANGUAGE.into());
+        Self(tag)
+    }
+
+    /// Primary language subtag (`pt` for `pt-BR`)
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or(&self.0)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn system_language() -> Option<String> {
+    web_sys::window()?.navigator().language()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn system_language() -> Option<String> {
+    // POSIX locale, e.g. `pt_BR.UTF-8`
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .map(|value| {
+            value
+                .split(['.', '@'])
+                .next()
+                .unwrap_or_default()
+                .replace('_', "-")
+        })
+}
+
+/// Manifest string, optionally translated
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum LocalizedText {
+    Plain(String),
+    /// Language tag → text, e.g. `{"en": "Garden", "pt": "Jardim"}`
+    Translated(HashMap<String, String>),
+}
+
+impl LocalizedText {
+    /// Exact tag, then the primary language, then English, then any translation
+    pub fn get(&self, locale: &Locale) -> &str {
+        match self {
+            Self::Plain(text) => text,
+            Self::Translated(map) => map
+                .get(&locale.0)
+                .or_else(|| map.get(locale.language()))
+                .or_else(|| {
+                    map.iter()
+                        .find(|(tag, _)| tag.split('-').next() == Some(locale.language()))
+                        .map(|(_, text)| text)
+                })
+                .or_else(|| map.get(FALLBACK_LANGUAGE))
+                .or_else(|| map.values().next())
+                .map_or("", String::as_str),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Room graph layout - spring embedding of the portal graph into the unit square
+
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use crate::portals::get_doors;
+use crate::tour::Tour;
+
+const ITERATIONS: usize = 300;
+/// Map margin so nodes and labels stay inside the panel
+const MARGIN: f32 = 0.12;
+
+/// Unique undirected portal connections
+pub fn room_links(tour: &Tour) -> Vec<(usize, usize)> {
+    let mut links = Vec::new();
+    for room in 0..tour.room_count() {
+        for door in get_doors(tour, room) {
+            let link = (room.min(door.target_room), room.max(door.target_room));
+            if link.0 != link.1 && !links.contains(&link) {
+                links.push(link);
+            }
+        }
+    }
+    links
+}
+
+/// Node positions in 0..1 (y down) - linked rooms pull together, all rooms push apart
+pub fn layout_rooms(count: usize, links: &[(usize, usize)]) -> Vec<Vec2> {
+    // Deterministic start on a circle so the map doesn't reshuffle between visits
+    let mut pos: Vec<Vec2> = (0..count)
+        .map(|i| Vec2::from_angle(TAU * i as f32 / count.max(1) as f32 - TAU / 4.0) * 0.5)
+        .collect();
+    if count < 3 {
+        return normalize(pos);
+    }
+
+    let ideal = (1.0 / count as f32).sqrt();
+    for step in 0..ITERATIONS {
+        let cooling = 0.1 * (1.0 - step as f32 / ITERATIONS as f32);
+        let mut force = vec![Vec2::ZERO; count];
+        for a in 0..count {
+            for b in (a + 1)..count {
+                let d = pos[a] - pos[b];
+                let dist = d.length().max(0.01);
+                let push = d / dist * (ideal * ideal / dist);
+                force[a] += push;
+                force[b] -= push;
+            }
+        }
+        for &(a, b) in links {
+            let d = pos[a] - pos[b];
+            let dist = d.length().max(0.01);
+            let pull = d / dist * (dist * dist / ideal);
+            force[a] -= pull;
+            force[b] += pull;
+        }
+        for (p, f) in pos.iter_mut().zip(force) {
+            *p += f.clamp_length_max(cooling);
+        }
+    }
+    normalize(pos)
+}
+
+/// Fit positions into the margin box, keeping the aspect ratio
+fn normalize(pos: Vec<Vec2>) -> Vec<Vec2> {
+    let min = pos.iter().copied().reduce(Vec2::min).unwrap_or_default();
+    let max = pos.iter().copied().reduce(Vec2::max).unwrap_or_default();
+    let span = (max - min).max_element().max(1e-3);
+    let offset = (Vec2::splat(span) - (max - min)) * 0.5;
+    pos.into_iter()
+        .map(|p| Vec2::splat(MARGIN) + (p - min + offset) / span * (1.0 - 2.0 * MARGIN))
+        .collect()
+}
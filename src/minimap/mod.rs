@@ -0,0 +1,448 @@
+//! Minimap - floor plan of the tour's room graph
+//!
+//! Rooms are nodes laid out from the portal graph, with the current room,
+//! the player's position and heading inside it, and rooms already visited.
+//! `M` cycles mini → full → hidden; in the full map, clicking a room travels
+//! there, unless a sealed portal still bars the way.
+
+mod layout;
+
+use bevy::prelude::*;
+use bevy::ui::Val::*;
+use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
+use std::collections::HashSet;
+
+use crate::book_reader::BookTheme;
+use crate::camera::CameraState;
+use crate::input::{InputState, UiWantsPointer};
+use crate::player::PlayerState;
+use crate::portals::{PortalLocks, TravelToRoom};
+use crate::tour::Tour;
+use crate::GameState;
+use layout::{layout_rooms, room_links};
+
+const MINI_SIZE: f32 = 180.0;
+const MINI_NODE: f32 = 14.0;
+const FULL_NODE: f32 = 30.0;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MinimapState>()
+            .add_systems(OnEnter(GameState::Viewing), setup_minimap)
+            .add_systems(
+                Update,
+                (
+                    toggle_minimap_input,
+                    handle_map_buttons,
+                    track_visited,
+                    update_minimap_layout.run_if(resource_changed::<MinimapState>),
+                    update_room_nodes,
+                    update_player_marker,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Viewing)),
+            );
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MapView {
+    Hidden,
+    #[default]
+    Mini,
+    Full,
+}
+
+#[derive(Resource, Default)]
+pub struct MinimapState {
+    pub view: MapView,
+    pub visited: HashSet<usize>,
+}
+
+// === Components ===
+
+#[derive(Component)]
+pub struct MinimapPanel;
+
+/// Square area the graph is drawn in (percent coordinates)
+#[derive(Component)]
+pub struct MinimapCanvas;
+
+#[derive(Component)]
+pub struct MapNode(pub usize);
+
+#[derive(Component)]
+pub struct MapNodeLabel;
+
+#[derive(Component)]
+pub struct MapCloseButton;
+
+#[derive(Component)]
+pub struct MapHint;
+
+#[derive(Component)]
+pub struct PlayerMarker;
+
+/// Walkable-area bounds of each room, to place the player marker inside its node
+#[derive(Resource)]
+struct RoomExtents(Vec<Rect>);
+
+// === Setup ===
+
+fn setup_minimap(mut cmd: Commands, tour: Res<Tour>, theme: Res<BookTheme>) {
+    let links = room_links(&tour);
+    let positions = layout_rooms(tour.room_count(), &links);
+
+    cmd.insert_resource(RoomExtents(
+        tour.rooms
+            .iter()
+            .map(|room| {
+                let bounds = &room.walkable.bounds;
+                let min = bounds.iter().copied().reduce(Vec2::min).unwrap_or_default();
+                let max = bounds.iter().copied().reduce(Vec2::max).unwrap_or_default();
+                Rect::from_corners(min, max)
+            })
+            .collect(),
+    ));
+
+    cmd.spawn((
+        MinimapPanel,
+        Button,
+        Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            padding: UiRect::all(Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(theme.bg),
+        BorderRadius::all(Px(12.0)),
+        GlobalZIndex(60),
+    ))
+    .with_children(|panel| {
+        panel
+            .spawn((
+                MapCloseButton,
+                Button,
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Px(8.0),
+                    right: Px(8.0),
+                    width: Px(32.0),
+                    height: Px(32.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+                BorderRadius::all(Px(8.0)),
+                ZIndex(1),
+            ))
+            .with_child((
+                Text::new("✕"),
+                TextFont::from_font_size(18.0),
+                TextColor(theme.muted),
+            ));
+
+        panel
+            .spawn((
+                MinimapCanvas,
+                Node {
+                    width: Percent(100.0),
+                    aspect_ratio: Some(1.0),
+                    ..default()
+                },
+            ))
+            .with_children(|canvas| {
+                for &(a, b) in &links {
+                    let (pa, pb) = (positions[a], positions[b]);
+                    let mid = (pa + pb) * 0.5;
+                    let length = pa.distance(pb);
+                    canvas.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Percent((mid.x - length * 0.5) * 100.0),
+                            top: Percent(mid.y * 100.0),
+                            width: Percent(length * 100.0),
+                            height: Px(2.0),
+                            ..default()
+                        },
+                        UiTransform::from_rotation(Rot2::radians((pb - pa).to_angle())),
+                        BackgroundColor(theme.muted.with_alpha(0.6)),
+                    ));
+                }
+
+                for (room, pos) in positions.iter().enumerate() {
+                    canvas
+                        .spawn((
+                            MapNode(room),
+                            Button,
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Percent(pos.x * 100.0),
+                                top: Percent(pos.y * 100.0),
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor(theme.surface),
+                            BorderColor::all(theme.muted),
+                            BorderRadius::MAX,
+                        ))
+                        .with_child((
+                            MapNodeLabel,
+                            Text::new(tour.rooms[room].name.clone()),
+                            TextFont::from_font_size(12.0),
+                            TextColor(theme.text),
+                            TextLayout::new_with_no_wrap(),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                top: Percent(110.0),
+                                ..default()
+                            },
+                        ));
+                }
+            });
+
+        panel.spawn((
+            MapHint,
+            Text::new("[M] Close map  •  Click a room to travel"),
+            TextFont::from_font_size(11.0),
+            TextColor(theme.muted),
+        ));
+    });
+
+    cmd.spawn((
+        PlayerMarker,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Px(10.0),
+            height: Px(10.0),
+            margin: UiRect::all(Px(-5.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![(
+            Text::new("▲"),
+            TextFont::from_font_size(10.0),
+            TextColor(theme.accent),
+            TextLayout::new_with_no_wrap(),
+        )],
+    ));
+
+    info!(
+        "🗺️ Minimap: {} rooms, {} links",
+        tour.room_count(),
+        links.len()
+    );
+}
+
+// === Systems ===
+
+fn toggle_minimap_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<MinimapState>,
+    mut input: ResMut<InputState>,
+    mut ui_wants: ResMut<UiWantsPointer>,
+    mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        state.view = match state.view {
+            MapView::Hidden => MapView::Mini,
+            MapView::Mini => MapView::Full,
+            MapView::Full => MapView::Hidden,
+        };
+    }
+    if keyboard.just_pressed(KeyCode::Escape) && state.view == MapView::Full {
+        state.view = MapView::Mini;
+    }
+
+    // The full map needs a free cursor to click rooms
+    let full = state.view == MapView::Full;
+    if ui_wants.0 != full {
+        ui_wants.0 = full;
+    }
+    if full && input.cursor_locked {
+        if let Ok(mut cursor) = cursor_q.single_mut() {
+            cursor.grab_mode = CursorGrabMode::None;
+            cursor.visible = true;
+        }
+        input.cursor_locked = false;
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn handle_map_buttons(
+    mut state: ResMut<MinimapState>,
+    player: Res<PlayerState>,
+    input: Res<InputState>,
+    panel: Query<&Interaction, (With<MinimapPanel>, Changed<Interaction>)>,
+    close: Query<&Interaction, (With<MapCloseButton>, Changed<Interaction>)>,
+    nodes: Query<(&Interaction, &MapNode), Changed<Interaction>>,
+    mut travel: MessageWriter<TravelToRoom>,
+    locks: Res<PortalLocks>,
+    tour: Res<Tour>,
+) {
+    match state.view {
+        MapView::Full => {
+            if close.iter().any(|i| *i == Interaction::Pressed) {
+                state.view = MapView::Mini;
+                return;
+            }
+            for (interaction, node) in nodes.iter() {
+                if *interaction == Interaction::Pressed
+                    && node.0 != player.room
+                    && locks.barrier(&tour, player.room, node.0).is_none()
+                {
+                    travel.write(TravelToRoom { room: node.0 });
+                    state.view = MapView::Mini;
+                    return;
+                }
+            }
+        }
+        // Tapping the minimap opens the full map (touch has no M key)
+        MapView::Mini => {
+            if !input.cursor_locked && panel.iter().any(|i| *i == Interaction::Pressed) {
+                state.view = MapView::Full;
+            }
+        }
+        MapView::Hidden => {}
+    }
+}
+
+fn track_visited(player: Res<PlayerState>, mut state: ResMut<MinimapState>) {
+    if !state.visited.contains(&player.room) {
+        state.visited.insert(player.room);
+    }
+}
+
+/// Corner minimap or centred full map
+#[allow(clippy::type_complexity)]
+fn update_minimap_layout(
+    state: Res<MinimapState>,
+    mut panel: Query<(&mut Node, &mut Visibility), With<MinimapPanel>>,
+    mut nodes: Query<&mut Node, (With<MapNode>, Without<MinimapPanel>)>,
+    mut hidden: Query<
+        &mut Node,
+        (
+            Or<(With<MapNodeLabel>, With<MapCloseButton>, With<MapHint>)>,
+            Without<MapNode>,
+            Without<MinimapPanel>,
+        ),
+    >,
+) {
+    let Ok((mut node, mut vis)) = panel.single_mut() else {
+        return;
+    };
+    let full = state.view == MapView::Full;
+    *vis = if state.view == MapView::Hidden {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+
+    if full {
+        node.width = Vh(80.0);
+        node.max_width = Percent(95.0);
+        node.left = Percent(50.0);
+        node.top = Percent(50.0);
+        node.right = Auto;
+        node.margin = UiRect::new(Vh(-40.0), Auto, Vh(-42.0), Auto);
+    } else {
+        node.width = Px(MINI_SIZE);
+        node.max_width = Auto;
+        node.left = Auto;
+        node.top = Px(16.0);
+        node.right = Px(16.0);
+        node.margin = UiRect::ZERO;
+    }
+
+    let size = if full { FULL_NODE } else { MINI_NODE };
+    for mut node in nodes.iter_mut() {
+        node.width = Px(size);
+        node.height = Px(size);
+        node.margin = UiRect::all(Px(-size * 0.5));
+        node.border = UiRect::all(Px(2.0));
+    }
+    for mut node in hidden.iter_mut() {
+        node.display = if full { Display::Flex } else { Display::None };
+    }
+}
+
+fn update_room_nodes(
+    state: Res<MinimapState>,
+    player: Res<PlayerState>,
+    theme: Res<BookTheme>,
+    locks: Res<PortalLocks>,
+    tour: Res<Tour>,
+    mut nodes: Query<(
+        &MapNode,
+        &Interaction,
+        &mut BackgroundColor,
+        &mut BorderColor,
+    )>,
+) {
+    if state.view == MapView::Hidden {
+        return;
+    }
+    // Sealed rooms can't be travelled to: dimmed, no hover
+    let barred = locks.barred_rooms(&tour, player.room);
+    for (node, interaction, mut bg, mut border) in nodes.iter_mut() {
+        let current = node.0 == player.room;
+        let visited = state.visited.contains(&node.0);
+        let sealed = barred.contains(&node.0);
+        let hovered = state.view == MapView::Full && *interaction != Interaction::None && !sealed;
+
+        let fill = if current {
+            theme.accent
+        } else if sealed {
+            theme.bg.with_alpha(0.4)
+        } else if visited {
+            theme.surface.with_alpha(1.0)
+        } else {
+            theme.bg
+        };
+        bg.set_if_neq(BackgroundColor(fill));
+        border.set_if_neq(BorderColor::all(if hovered {
+            theme.text
+        } else if sealed {
+            theme.muted.with_alpha(0.2)
+        } else if visited {
+            theme.accent
+        } else {
+            theme.muted.with_alpha(0.5)
+        }));
+    }
+}
+
+/// Keep the heading arrow inside the current room's node
+fn update_player_marker(
+    mut cmd: Commands,
+    player: Res<PlayerState>,
+    camera: Res<CameraState>,
+    extents: Res<RoomExtents>,
+    nodes: Query<(Entity, &MapNode)>,
+    mut marker: Query<(Entity, &mut Node, &mut UiTransform, Option<&ChildOf>), With<PlayerMarker>>,
+) {
+    let Ok((entity, mut node, mut transform, parent)) = marker.single_mut() else {
+        return;
+    };
+    let Some((room_node, _)) = nodes.iter().find(|(_, n)| n.0 == player.room) else {
+        return;
+    };
+    if parent.map(ChildOf::parent) != Some(room_node) {
+        cmd.entity(room_node).add_child(entity);
+    }
+
+    let Some(extent) = extents.0.get(player.room) else {
+        return;
+    };
+    let half = extent.half_size().max_element().max(0.01);
+    let offset = ((player.pos - extent.center()) / half).clamp_length_max(1.0);
+    node.left = Percent(50.0 + offset.x * 50.0);
+    node.top = Percent(50.0 + offset.y * 50.0);
+    // Yaw turns counterclockwise seen from above; UI rotation is clockwise
+    transform.rotation = Rot2::radians(-camera.yaw);
+}
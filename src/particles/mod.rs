@@ -8,7 +8,8 @@ use bevy_hanabi::Gradient as HanabiGradient;
 
 use crate::performance::{QualityChanged, QualitySettings};
 use crate::player::PlayerState;
-use crate::world::{room_center, TOTAL_ROOMS};
+use crate::tour::Tour;
+use crate::world::room_center;
 use crate::GameState;
 
 pub struct GpuParticlesPlugin;
@@ -42,6 +43,7 @@ fn spawn_room_particles(
     mut commands: Commands,
     mut effects: ResMut<Assets<EffectAsset>>,
     quality: Res<QualitySettings>,
+    tour: Res<Tour>,
 ) {
     let mult = quality.level.spawner_rate_mult();
 
@@ -55,7 +57,7 @@ fn spawn_room_particles(
     let circles_h = effects.add(circles);
     let sparkles_h = effects.add(sparkles);
 
-    for room in 0..TOTAL_ROOMS {
+    for room in 0..tour.room_count() {
         let center = room_center(room);
         let base = center - Vec3::Z * 8.0;
 
@@ -89,7 +91,8 @@ fn spawn_room_particles(
 
     info!(
         "✨ GPU particles: {} rooms, quality {:?}",
-        TOTAL_ROOMS, quality.level
+        tour.room_count(),
+        quality.level
     );
 }
 
@@ -57,6 +57,26 @@ impl QualityLevel {
             Self::Potato => 0.08,
         }
     }
+
+    /// Portal render target resolution multiplier for this quality level
+    pub fn portal_resolution_scale(&self) -> f32 {
+        match self {
+            Self::Ultra => 1.5,
+            Self::High => 1.0,
+            Self::Medium => 0.75,
+            Self::Low => 0.5,
+            Self::Potato => 0.35,
+        }
+    }
+
+    /// Portals visible through portals, levels deep, for this quality level
+    pub fn portal_depth(&self) -> usize {
+        match self {
+            Self::Ultra | Self::High => 2,
+            Self::Medium | Self::Low => 1,
+            Self::Potato => 0,
+        }
+    }
 }
 
 /// Event fired when quality changes
@@ -111,6 +131,8 @@ pub struct QualitySettings {
     pub effect_intensity: f32,
     pub max_lights: u32,
     pub material_update_hz: f32,
+    /// GPU memory for streamed panorama tiles
+    pub tile_budget_mb: u32,
     /// Hysteresis: FPS must drop below this to downgrade
     downgrade_threshold: f32,
     /// Hysteresis: FPS must exceed this to upgrade
@@ -130,6 +152,7 @@ impl Default for QualitySettings {
             effect_intensity: 1.0,
             max_lights: 8,
             material_update_hz: 20.0,
+            tile_budget_mb: 128,
             downgrade_threshold: 28.0,
             upgrade_threshold: 55.0,
         };
@@ -147,30 +170,35 @@ impl QualitySettings {
                 self.effect_intensity = 1.0;
                 self.max_lights = 16;
                 self.material_update_hz = 60.0;
+                self.tile_budget_mb = 512;
             }
             QualityLevel::High => {
                 self.particle_multiplier = 0.7;
                 self.effect_intensity = 1.0;
                 self.max_lights = 12;
                 self.material_update_hz = 30.0;
+                self.tile_budget_mb = 256;
             }
             QualityLevel::Medium => {
                 self.particle_multiplier = 0.4;
                 self.effect_intensity = 0.8;
                 self.max_lights = 8;
                 self.material_update_hz = 20.0;
+                self.tile_budget_mb = 128;
             }
             QualityLevel::Low => {
                 self.particle_multiplier = 0.2;
                 self.effect_intensity = 0.5;
                 self.max_lights = 4;
                 self.material_update_hz = 10.0;
+                self.tile_budget_mb = 64;
             }
             QualityLevel::Potato => {
                 self.particle_multiplier = 0.08;
                 self.effect_intensity = 0.0;
                 self.max_lights = 2;
                 self.material_update_hz = 5.0;
+                self.tile_budget_mb = 24;
             }
         }
     }
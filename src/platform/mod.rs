@@ -12,7 +12,7 @@ pub use desktop::DesktopPlatformPlugin;
 #[cfg(feature = "vr")]
 pub use vr::VrPlatformPlugin;
 #[cfg(feature = "webxr")]
-pub use webxr::{WebXrPlatformPlugin, WebXrPose, WebXrState};
+pub use webxr::{WebXrPlatformPlugin, WebXrPose, WebXrState, XrViewData};
 
 use crate::core::{Os, PlatformEntity};
 use bevy::prelude::*;
@@ -7,6 +7,7 @@ use wasm_bindgen_futures::spawn_local;
 
 use crate::camera::{CameraState, GameCamera};
 use crate::input::InputState;
+use crate::world::Eye;
 use crate::Platform;
 
 /// WebXR session state
@@ -23,6 +24,18 @@ pub struct XrPoseData {
     pub position: [f32; 3],
     pub orientation: [f32; 4],
     pub valid: bool,
+    /// Per-eye views of the frame (empty for a mono session)
+    pub views: Vec<XrViewData>,
+}
+
+/// One eye's view in the XR reference space
+#[derive(Clone)]
+pub struct XrViewData {
+    pub eye: Eye,
+    pub position: [f32; 3],
+    pub orientation: [f32; 4],
+    /// Vertical field of view (radians)
+    pub fov: f32,
 }
 
 /// Thread-safe pose storage updated from JS
@@ -207,6 +220,31 @@ fn start_xr_frame_loop(
                     ori.w() as f32,
                 ];
                 data.valid = true;
+                data.views = pose
+                    .views()
+                    .iter()
+                    .map(|view| {
+                        let view = web_sys::XrView::from(view);
+                        let transform = view.transform();
+                        let (pos, ori) = (transform.position(), transform.orientation());
+                        // projection[5] = 1 / tan(fov_y / 2)
+                        let projection = view.projection_matrix();
+                        XrViewData {
+                            eye: match view.eye() {
+                                web_sys::XrEye::Right => Eye::Right,
+                                _ => Eye::Left,
+                            },
+                            position: [pos.x() as f32, pos.y() as f32, pos.z() as f32],
+                            orientation: [
+                                ori.x() as f32,
+                                ori.y() as f32,
+                                ori.z() as f32,
+                                ori.w() as f32,
+                            ],
+                            fov: 2.0 * (1.0 / projection[5]).atan(),
+                        }
+                    })
+                    .collect();
             }
         }
 
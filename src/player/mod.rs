@@ -1,20 +1,40 @@
 //! Player module - FPS movement, position, room state
 
+mod teleport;
+mod walkable;
+
+pub use teleport::TeleportState;
+pub use walkable::*;
+
 use bevy::camera::visibility::RenderLayers;
 use bevy::prelude::*;
 
+use crate::glb_character::CharacterConfig;
 use crate::input::InputState;
 use crate::panorama::PanoramaCamera;
-use crate::world::room_center;
+use crate::tour::{Tour, TourRoom};
+use crate::world::{room_center, room_view_layers, Eye};
 use crate::GameState;
+use teleport::{aim_teleport, run_teleport, setup_teleport, update_teleport_indicator};
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(PlayerState::default())
-            .add_systems(OnEnter(GameState::Viewing), init_player)
-            .add_systems(Update, player_movement.run_if(in_state(GameState::Viewing)));
+            .init_resource::<TeleportState>()
+            .add_systems(OnEnter(GameState::Viewing), (init_player, setup_teleport))
+            .add_systems(
+                Update,
+                (
+                    aim_teleport,
+                    run_teleport,
+                    player_movement,
+                    update_teleport_indicator,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Viewing)),
+            );
     }
 }
 
@@ -42,13 +62,15 @@ fn init_player(
     cam_q: Query<Entity, (With<PanoramaCamera>, Without<RenderLayers>)>,
 ) {
     for entity in cam_q.iter() {
-        cmd.entity(entity).insert(RenderLayers::layer(0));
+        cmd.entity(entity).insert(room_view_layers(0, Eye::Left));
     }
 }
 
 fn player_movement(
     time: Res<Time>,
     input: Res<InputState>,
+    tour: Res<Tour>,
+    character: Option<Res<CharacterConfig>>,
     mut state: ResMut<PlayerState>,
     mut cam_q: Query<&mut Transform, With<PanoramaCamera>>,
 ) {
@@ -75,23 +97,20 @@ fn player_movement(
 
     if move_dir.length() > 0.01 {
         let speed = 3.0;
-        let new_pos = state.pos + move_dir * speed * dt;
-
-        // Character barrier
-        let char_pos = Vec2::new(0.0, -10.0);
-        let to_char = new_pos - char_pos;
-        let char_dist = to_char.length();
-        let min_char_dist = 4.0;
-
-        let bounded_pos = if char_dist < min_char_dist && char_dist > 0.01 {
-            char_pos + to_char.normalize() * min_char_dist
-        } else {
-            new_pos
-        };
-
-        state.pos.x = bounded_pos.x.clamp(-8.0, 8.0);
-        state.pos.y = bounded_pos.y.clamp(-14.0, 3.0);
+        let room = &tour.rooms[state.room];
+        let barrier = character_barrier(room, character.as_deref());
+
+        state.pos = room
+            .walkable
+            .slide(state.pos, move_dir * speed * dt, barrier.as_slice());
     }
 
     cam.translation = room_center(state.room) + Vec3::new(state.pos.x, state.height, state.pos.y);
 }
+
+/// Circle around the room's character the player can't walk into
+fn character_barrier(room: &TourRoom, character: Option<&CharacterConfig>) -> Option<(Vec2, f32)> {
+    character
+        .filter(|_| room.character.is_some())
+        .map(|config| (config.position, config.barrier_radius))
+}
@@ -0,0 +1,325 @@
+//! Teleport locomotion - point at the floor, confirm, fade and arrive
+//!
+//! Desktop aims with the mouse (or view centre while the cursor is captured)
+//! and confirms with the right button; touch taps the floor. In XR sessions a
+//! parabolic arc leaves the head and lands on the floor; holding it steady
+//! on a spot confirms. Targets must leave room for the player inside the
+//! walkable area, clear of walls, obstacles and the room character.
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use super::{character_barrier, PlayerState};
+use crate::camera::GameCamera;
+use crate::glb_character::CharacterConfig;
+use crate::input::{InputState, UiWantsPointer};
+use crate::platform::Platform;
+use crate::tour::Tour;
+use crate::world::room_center;
+
+/// Furthest teleport (m)
+const MAX_DISTANCE: f32 = 10.0;
+/// Closer targets aren't worth a fade
+const MIN_DISTANCE: f32 = 0.5;
+/// Seconds to fade out (and back in)
+const FADE_TIME: f32 = 0.15;
+/// Seconds the XR arc must rest on a spot to teleport there
+const XR_DWELL: f32 = 1.5;
+/// XR aim may wobble this much (m) without restarting the dwell
+const DWELL_TOLERANCE: f32 = 0.4;
+/// A touch that moves less than this (px) and lifts within `TAP_TIME` is a tap
+const TAP_SLOP: f32 = 12.0;
+const TAP_TIME: f32 = 0.35;
+/// Arc launch speed (m/s) and gravity
+const ARC_SPEED: f32 = 8.0;
+const ARC_GRAVITY: f32 = 9.8;
+const ARC_DOTS: usize = 20;
+
+#[derive(Resource, Default)]
+pub struct TeleportState {
+    /// Valid room-local target under the aim, if any
+    pub target: Option<Vec2>,
+    /// Confirmed target waiting for the fade-out to finish
+    pending: Option<Vec2>,
+    /// 0 = clear, 1 = black
+    fade: f32,
+    dwell: f32,
+    dwell_anchor: Vec2,
+    tap: Option<(u64, Vec2, f32)>,
+    arc: Vec<Vec3>,
+}
+
+#[derive(Component)]
+pub struct TeleportCursor;
+
+#[derive(Component)]
+pub struct TeleportArcDot(usize);
+
+#[derive(Component)]
+pub struct TeleportFade;
+
+pub fn setup_teleport(
+    mut cmd: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+) {
+    let material = mats.add(StandardMaterial {
+        base_color: Color::srgba(0.6, 0.9, 1.0, 0.8),
+        emissive: LinearRgba::new(0.6, 1.4, 2.0, 1.0),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    });
+
+    cmd.spawn((
+        TeleportCursor,
+        Mesh3d(meshes.add(Annulus::new(0.28, 0.38))),
+        MeshMaterial3d(material.clone()),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+
+    let dot = meshes.add(Sphere::new(0.03));
+    for i in 0..ARC_DOTS {
+        cmd.spawn((
+            TeleportArcDot(i),
+            Mesh3d(dot.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::default(),
+            Visibility::Hidden,
+        ));
+    }
+
+    cmd.spawn((
+        TeleportFade,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        Pickable::IGNORE,
+        GlobalZIndex(200),
+    ));
+}
+
+/// Find the floor point under the aim and handle confirmation
+#[allow(clippy::too_many_arguments)]
+pub fn aim_teleport(
+    time: Res<Time>,
+    tour: Res<Tour>,
+    player: Res<PlayerState>,
+    character: Option<Res<CharacterConfig>>,
+    platform: Res<Platform>,
+    input: Res<InputState>,
+    ui_wants: Res<UiWantsPointer>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    mut state: ResMut<TeleportState>,
+    cameras: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Ok((camera, cam_tr)) = cameras.single() else {
+        return;
+    };
+    let room = &tour.rooms[player.room];
+    let center = room_center(player.room);
+    // Same clearance walking keeps, so the player lands where they could stand
+    let barrier = character_barrier(room, character.as_deref());
+    let valid = |hit: Vec3| {
+        let local = Vec2::new(hit.x - center.x, hit.z - center.z);
+        let dist = local.distance(player.pos);
+        ((MIN_DISTANCE..=MAX_DISTANCE).contains(&dist)
+            && room.walkable.fits(local, barrier.as_slice()))
+        .then_some(local)
+    };
+    let floor_hit = |ray: Ray3d| {
+        ray.intersect_plane(center, InfinitePlane3d::new(Vec3::Y))
+            .map(|t| ray.get_point(t))
+    };
+
+    state.arc.clear();
+    if state.pending.is_some() {
+        return;
+    }
+
+    // Touch: a short tap without dragging
+    let now = time.elapsed_secs();
+    for touch in touches.iter_just_pressed() {
+        state.tap = (touches.iter().count() == 1).then_some((touch.id(), touch.position(), now));
+    }
+    let mut tapped = None;
+    if let Some((id, start, at)) = state.tap {
+        if touches.iter().count() > 1 {
+            state.tap = None;
+        } else if let Some(touch) = touches.get_released(id) {
+            if touch.position().distance(start) < TAP_SLOP && now - at < TAP_TIME {
+                tapped = Some(touch.position());
+            }
+            state.tap = None;
+        }
+    }
+    if let Some(tap) = tapped {
+        let hit = camera
+            .viewport_to_world(cam_tr, tap)
+            .ok()
+            .and_then(floor_hit);
+        state.pending = hit.and_then(valid);
+        return;
+    }
+
+    // XR sessions capture input (the cursor counts as locked)
+    if *platform != Platform::Desktop && input.cursor_locked {
+        // Parabolic arc from the head
+        let mut pos = cam_tr.translation() - Vec3::Y * 0.2;
+        let mut vel = cam_tr.forward().as_vec3() * ARC_SPEED;
+        let dt = 0.05;
+        let mut landing = None;
+        for _ in 0..60 {
+            let next = pos + vel * dt;
+            vel.y -= ARC_GRAVITY * dt;
+            if next.y <= center.y {
+                let t = (pos.y - center.y) / (pos.y - next.y).max(1e-5);
+                landing = Some(pos.lerp(next, t));
+                break;
+            }
+            state.arc.push(next);
+            pos = next;
+        }
+        state.target = landing.and_then(valid);
+        if state.target.is_none() {
+            state.arc.clear();
+        }
+
+        // Dwell to confirm
+        match state.target {
+            Some(target) if target.distance(state.dwell_anchor) < DWELL_TOLERANCE => {
+                state.dwell += time.delta_secs();
+                if state.dwell >= XR_DWELL {
+                    state.pending = Some(target);
+                    state.dwell = 0.0;
+                }
+            }
+            Some(target) => {
+                state.dwell_anchor = target;
+                state.dwell = 0.0;
+            }
+            None => state.dwell = 0.0,
+        }
+        return;
+    }
+
+    // Mouse: view centre while captured, else the cursor
+    let ray = if input.cursor_locked {
+        Some(Ray3d::new(cam_tr.translation(), cam_tr.forward()))
+    } else if ui_wants.0 {
+        None
+    } else {
+        windows
+            .single()
+            .ok()
+            .and_then(|w| w.cursor_position())
+            .and_then(|cursor| camera.viewport_to_world(cam_tr, cursor).ok())
+    };
+    state.target = ray.and_then(floor_hit).and_then(valid);
+    if mouse.just_pressed(MouseButton::Right) {
+        state.pending = state.target;
+    }
+}
+
+/// Fade out, move, fade back in
+pub fn run_teleport(
+    time: Res<Time>,
+    mut state: ResMut<TeleportState>,
+    mut player: ResMut<PlayerState>,
+    mut fade_q: Query<&mut BackgroundColor, With<TeleportFade>>,
+) {
+    let step = time.delta_secs() / FADE_TIME;
+    if let Some(target) = state.pending {
+        state.fade = (state.fade + step).min(1.0);
+        if state.fade >= 1.0 {
+            player.pos = target;
+            player.prev_pos = target; // Don't count the jump as a portal crossing
+            state.pending = None;
+            state.target = None;
+            info!("✨ Teleport → ({:.1}, {:.1})", target.x, target.y);
+        }
+    } else if state.fade > 0.0 {
+        state.fade = (state.fade - step).max(0.0);
+    } else {
+        return;
+    }
+
+    if let Ok(mut bg) = fade_q.single_mut() {
+        bg.0 = Color::srgba(0.0, 0.0, 0.0, state.fade);
+    }
+}
+
+/// Floor cursor at the target and arc dots along the XR aim
+#[allow(clippy::type_complexity)]
+pub fn update_teleport_indicator(
+    time: Res<Time>,
+    state: Res<TeleportState>,
+    player: Res<PlayerState>,
+    mut cmd: Commands,
+    mut cursor: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Visibility,
+            Option<&RenderLayers>,
+        ),
+        With<TeleportCursor>,
+    >,
+    mut dots: Query<
+        (
+            Entity,
+            &TeleportArcDot,
+            &mut Transform,
+            &mut Visibility,
+            Option<&RenderLayers>,
+        ),
+        Without<TeleportCursor>,
+    >,
+) {
+    let layers = RenderLayers::layer(player.room);
+    let center = room_center(player.room);
+
+    if let Ok((entity, mut tr, mut vis, current)) = cursor.single_mut() {
+        match state.pending.or(state.target) {
+            Some(target) => {
+                let pulse = 1.0 + (time.elapsed_secs() * 5.0).sin() * 0.08;
+                *tr = Transform::from_translation(center + Vec3::new(target.x, 0.02, target.y))
+                    .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2))
+                    .with_scale(Vec3::splat(pulse));
+                vis.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                vis.set_if_neq(Visibility::Hidden);
+            }
+        }
+        if current != Some(&layers) {
+            cmd.entity(entity).insert(layers.clone());
+        }
+    }
+
+    let stride = (state.arc.len() / ARC_DOTS).max(1);
+    for (entity, dot, mut tr, mut vis, current) in dots.iter_mut() {
+        match state.arc.get(dot.0 * stride) {
+            Some(pos) => {
+                tr.translation = *pos;
+                vis.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                vis.set_if_neq(Visibility::Hidden);
+            }
+        }
+        if current != Some(&layers) {
+            cmd.entity(entity).insert(layers.clone());
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Walkable area - per-room floor polygon with obstacles
+//!
+//! Movement is split into short steps; after each one the player is pushed out
+//! of every edge and obstacle it overlaps, which slides it along walls instead
+//! of stopping dead.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Player collision radius (m)
+pub const PLAYER_RADIUS: f32 = 0.3;
+/// Longest move per collision step - shorter than the radius so edges can't be skipped
+const MAX_STEP: f32 = PLAYER_RADIUS * 0.5;
+/// Push-out passes per step (corners touch two edges at once)
+const RESOLVE_PASSES: usize = 3;
+
+/// Walkable area entry in the tour manifest (room-local XZ, metres)
+#[derive(Deserialize, Clone, Debug)]
+pub struct WalkableFile {
+    pub bounds: Vec<(f32, f32)>,
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleFile>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum ObstacleFile {
+    Circle { center: (f32, f32), radius: f32 },
+    Polygon(Vec<(f32, f32)>),
+}
+
+#[derive(Clone, Debug)]
+pub enum Obstacle {
+    Circle { center: Vec2, radius: f32 },
+    Polygon(Vec<Vec2>),
+}
+
+/// Floor polygon the player stays inside, minus obstacles
+#[derive(Clone, Debug)]
+pub struct WalkableArea {
+    pub bounds: Vec<Vec2>,
+    pub obstacles: Vec<Obstacle>,
+}
+
+impl Default for WalkableArea {
+    /// The box every room used before layouts were configurable
+    fn default() -> Self {
+        Self {
+            bounds: vec![
+                Vec2::new(-8.0, -14.0),
+                Vec2::new(8.0, -14.0),
+                Vec2::new(8.0, 3.0),
+                Vec2::new(-8.0, 3.0),
+            ],
+            obstacles: Vec::new(),
+        }
+    }
+}
+
+impl From<WalkableFile> for WalkableArea {
+    fn from(file: WalkableFile) -> Self {
+        let points = |pts: Vec<(f32, f32)>| pts.into_iter().map(Vec2::from).collect::<Vec<_>>();
+        if file.bounds.len() < 3 {
+            warn!("⚠️ Walkable bounds need at least 3 points - using the default area");
+            return Self::default();
+        }
+        Self {
+            bounds: points(file.bounds),
+            obstacles: file
+                .obstacles
+                .into_iter()
+                .filter_map(|obstacle| match obstacle {
+                    ObstacleFile::Circle { center, radius } => Some(Obstacle::Circle {
+                        center: center.into(),
+                        radius,
+                    }),
+                    ObstacleFile::Polygon(pts) if pts.len() >= 3 => {
+                        Some(Obstacle::Polygon(points(pts)))
+                    }
+                    ObstacleFile::Polygon(_) => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl WalkableArea {
+    /// Whether the point is on the floor and outside every obstacle
+    pub fn contains(&self, p: Vec2) -> bool {
+        inside_polygon(&self.bounds, p)
+            && self.obstacles.iter().all(|obstacle| match obstacle {
+                Obstacle::Circle { center, radius } => p.distance(*center) >= *radius,
+                Obstacle::Polygon(pts) => !inside_polygon(pts, p),
+            })
+    }
+
+    /// Whether the player's whole circle fits at `p`: on the floor and clear of
+    /// every edge and obstacle by `PLAYER_RADIUS`; `extra` as in `slide`
+    pub fn fits(&self, p: Vec2, extra: &[(Vec2, f32)]) -> bool {
+        let clear_of = |pts: &[Vec2]| {
+            edges(pts).all(|(a, b)| p.distance(closest_on_segment(p, a, b)) >= PLAYER_RADIUS)
+        };
+        let circles = self.obstacles.iter().filter_map(|obstacle| match obstacle {
+            Obstacle::Circle { center, radius } => Some((*center, *radius)),
+            Obstacle::Polygon(_) => None,
+        });
+        self.contains(p)
+            && clear_of(&self.bounds)
+            && circles
+                .chain(extra.iter().copied())
+                .all(|(center, radius)| p.distance(center) >= radius + PLAYER_RADIUS)
+            && self.obstacles.iter().all(|obstacle| match obstacle {
+                Obstacle::Polygon(pts) => clear_of(pts),
+                Obstacle::Circle { .. } => true,
+            })
+    }
+
+    /// Move from `from` by `delta`, sliding along edges; `extra` adds
+    /// circular obstacles (e.g. the room character's barrier)
+    pub fn slide(&self, from: Vec2, delta: Vec2, extra: &[(Vec2, f32)]) -> Vec2 {
+        let steps = (delta.length() / MAX_STEP).ceil().max(1.0);
+        let step = delta / steps;
+        let mut pos = from;
+
+        for _ in 0..steps as usize {
+            let mut next = pos + step;
+            for _ in 0..RESOLVE_PASSES {
+                next = self.push_out(next, extra);
+            }
+            // Never leave the area (a step can't skip an edge, but corners can pinch)
+            if self.contains(pos) && !self.contains(next) {
+                break;
+            }
+            pos = next;
+        }
+        pos
+    }
+
+    fn push_out(&self, mut p: Vec2, extra: &[(Vec2, f32)]) -> Vec2 {
+        for (a, b) in edges(&self.bounds) {
+            p = push_from_segment(p, a, b);
+        }
+        let circles = self.obstacles.iter().filter_map(|obstacle| match obstacle {
+            Obstacle::Circle { center, radius } => Some((*center, *radius)),
+            Obstacle::Polygon(_) => None,
+        });
+        for (center, radius) in circles.chain(extra.iter().copied()) {
+            let away = p - center;
+            let min = radius + PLAYER_RADIUS;
+            if away.length() < min {
+                p = center + away.try_normalize().unwrap_or(Vec2::Y) * min;
+            }
+        }
+        for obstacle in &self.obstacles {
+            if let Obstacle::Polygon(pts) = obstacle {
+                for (a, b) in edges(pts) {
+                    p = push_from_segment(p, a, b);
+                }
+            }
+        }
+        p
+    }
+}
+
+fn edges(pts: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    pts.iter().copied().zip(pts.iter().copied().cycle().skip(1))
+}
+
+fn closest_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let t = ((p - a).dot(ab) / ab.length_squared().max(1e-6)).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+/// Keep the player's circle off a wall segment, on the side it's already on
+fn push_from_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let closest = closest_on_segment(p, a, b);
+    let away = p - closest;
+    let dist = away.length();
+    if !(1e-5..PLAYER_RADIUS).contains(&dist) {
+        return p;
+    }
+    closest + away / dist * PLAYER_RADIUS
+}
+
+/// Even-odd point-in-polygon test
+fn inside_polygon(pts: &[Vec2], p: Vec2) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(pts) {
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
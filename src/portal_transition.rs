@@ -1,89 +1,223 @@
-//! Portal Transition Effects - Seamless visual transitions when crossing portals
+//! Portal Transition Effects - Full-screen overlay that builds up as the player
+//! nears a door and hides the cut when they cross it. Jumps (map travel,
+//! hotspots) cover the view fully before their cut.
 
 use bevy::prelude::*;
 use bevy::render::render_resource::AsBindGroup;
 use bevy::shader::ShaderRef;
+use serde::Deserialize;
 
+use crate::player::PlayerState;
+use crate::portals::{PortalCrossed, PortalDoor, PORTAL_WIDTH};
+use crate::tour::Tour;
 use crate::GameState;
-use crate::portal_doors::RoomState;
 
 pub struct PortalTransitionPlugin;
 
 impl Plugin for PortalTransitionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(MaterialPlugin::<TransitionOverlayMaterial>::default())
-            .init_resource::<TransitionState>()
-            .add_systems(Update, (
-                detect_portal_approach,
-                animate_transition,
-            ).chain().run_if(in_state(GameState::Viewing)));
+        app.add_plugins(UiMaterialPlugin::<TransitionOverlayMaterial>::default())
+            .init_resource::<PortalTransitionState>()
+            .add_systems(OnEnter(GameState::Viewing), setup_transition_overlay)
+            .add_systems(
+                Update,
+                (detect_portal_approach, animate_transition, update_overlay)
+                    .chain()
+                    .run_if(in_state(GameState::Viewing)),
+            );
     }
 }
 
+/// Overlay starts building up this far (m) in front of a door
+const APPROACH_RANGE: f32 = 1.0;
+/// Strongest overlay from approaching alone - crossing covers the rest
+const APPROACH_MAX: f32 = 0.6;
+
+/// How the overlay covers and reveals the view
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransitionStyle {
+    /// Plain fade through the overlay colour
+    Fade,
+    /// Noise dissolve
+    Dissolve,
+    /// Radial wipe with swirling energy
+    #[default]
+    Wipe,
+}
+
+impl TransitionStyle {
+    fn shader_index(self) -> f32 {
+        match self {
+            Self::Fade => 0.0,
+            Self::Dissolve => 1.0,
+            Self::Wipe => 2.0,
+        }
+    }
+}
+
+/// Transition entry in the tour manifest
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct TransitionConfig {
+    #[serde(default)]
+    pub style: TransitionStyle,
+    /// Seconds to reveal the new room after crossing
+    #[serde(default = "default_duration")]
+    pub duration: f32,
+}
+
+impl Default for TransitionConfig {
+    fn default() -> Self {
+        Self {
+            style: TransitionStyle::default(),
+            duration: default_duration(),
+        }
+    }
+}
+
+fn default_duration() -> f32 {
+    0.6
+}
+
 #[derive(Resource, Default)]
-pub struct TransitionState {
+pub struct PortalTransitionState {
+    /// Playing the reveal after a crossing
     pub active: bool,
+    /// Covering the view for a jump, which cuts once `progress` reaches 1
+    pub covering: bool,
+    /// 0 = clear, 1 = covered
     pub progress: f32,
-    pub from_room: usize,
-    pub to_room: usize,
-    pub fade_in: bool,
+    /// Build-up from the nearest door
+    pub approach: f32,
 }
 
 #[derive(Asset, TypePath, AsBindGroup, Clone)]
 pub struct TransitionOverlayMaterial {
     #[uniform(0)]
+    pub settings: TransitionSettings,
+}
+
+#[derive(Debug, Clone, Copy, Default, bevy::render::render_resource::ShaderType)]
+pub struct TransitionSettings {
     pub progress: f32,
-    #[uniform(1)]
-    pub direction: f32, // 1.0 = fade out, -1.0 = fade in
+    pub direction: f32, // 1.0 = covering (approach), -1.0 = revealing
+    pub style: f32,     // `TransitionStyle::shader_index`
+    pub time: f32,
 }
 
-impl Material for TransitionOverlayMaterial {
+impl UiMaterial for TransitionOverlayMaterial {
     fn fragment_shader() -> ShaderRef {
         "shaders/portal_transition.wgsl".into()
     }
-    
-    fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
-    }
 }
 
+#[derive(Component)]
+struct TransitionOverlay;
+
+fn setup_transition_overlay(
+    mut cmd: Commands,
+    mut mats: ResMut<Assets<TransitionOverlayMaterial>>,
+    mut state: ResMut<PortalTransitionState>,
+    tour: Res<Tour>,
+) {
+    *state = PortalTransitionState::default();
+    cmd.spawn((
+        TransitionOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        MaterialNode(mats.add(TransitionOverlayMaterial {
+            settings: TransitionSettings {
+                style: tour.transition.style.shader_index(),
+                ..default()
+            },
+        })),
+        Pickable::IGNORE,
+        GlobalZIndex(150),
+        Visibility::Hidden,
+    ));
+}
+
+/// Build the overlay up as the player walks at a door
 fn detect_portal_approach(
-    room_state: Res<RoomState>,
-    mut trans_state: ResMut<TransitionState>,
+    player: Res<PlayerState>,
+    portals: Query<&PortalDoor>,
+    mut state: ResMut<PortalTransitionState>,
 ) {
-    // Detect when player is very close to a portal
-    let door_positions = [
-        bevy::math::Vec2::new(-4.0, -4.0),
-        bevy::math::Vec2::new(4.0, -4.0),
-    ];
-    
-    for door_pos in door_positions {
-        let dist = room_state.pos.distance(door_pos);
-        if dist < 1.5 && !trans_state.active {
-            // Start subtle transition effect
-            trans_state.progress = (1.5 - dist) / 1.5;
-        }
-    }
+    state.approach = portals
+        .iter()
+        .filter(|door| door.room == player.room)
+        .filter_map(|door| {
+            let normal = Vec2::new(door.rotation.sin(), -door.rotation.cos());
+            let offset = player.pos - door.local_pos;
+            let depth = offset.dot(normal).abs();
+            let lateral = (offset - normal * offset.dot(normal)).length();
+            (depth < APPROACH_RANGE && lateral < PORTAL_WIDTH).then(|| 1.0 - depth / APPROACH_RANGE)
+        })
+        .fold(0.0, f32::max)
+        * APPROACH_MAX;
 }
 
 fn animate_transition(
     time: Res<Time>,
-    mut trans_state: ResMut<TransitionState>,
+    tour: Res<Tour>,
+    mut crossings: MessageReader<PortalCrossed>,
+    mut state: ResMut<PortalTransitionState>,
 ) {
-    if !trans_state.active { return; }
-    
-    let dt = time.delta_secs();
-    
-    if trans_state.fade_in {
-        trans_state.progress -= dt * 3.0;
-        if trans_state.progress <= 0.0 {
-            trans_state.active = false;
-            trans_state.progress = 0.0;
-        }
+    let step = time.delta_secs() / tour.transition.duration.max(0.05);
+    if let Some(crossed) = crossings.read().last() {
+        state.active = true;
+        state.covering = false;
+        state.progress = 1.0;
+        debug!(
+            "🌀 Transition {:?}: room {} → {}",
+            tour.transition.style,
+            crossed.from_room + 1,
+            crossed.to_room + 1
+        );
+    }
+    if state.covering {
+        state.progress = (state.progress.max(state.approach) + step).min(1.0);
+        return;
+    }
+    if !state.active {
+        return;
+    }
+
+    state.progress -= step;
+    if state.progress <= 0.0 {
+        state.active = false;
+        state.progress = 0.0;
+    }
+}
+
+fn update_overlay(
+    time: Res<Time>,
+    state: Res<PortalTransitionState>,
+    mut mats: ResMut<Assets<TransitionOverlayMaterial>>,
+    mut overlay: Query<(&MaterialNode<TransitionOverlayMaterial>, &mut Visibility)>,
+) {
+    let Ok((handle, mut vis)) = overlay.single_mut() else {
+        return;
+    };
+    let (progress, direction) = if state.covering {
+        (state.progress, 1.0)
+    } else if state.active {
+        (state.progress.max(state.approach), -1.0)
     } else {
-        trans_state.progress += dt * 3.0;
-        if trans_state.progress >= 1.0 {
-            trans_state.fade_in = true;
-        }
+        (state.approach, 1.0)
+    };
+
+    if progress <= 0.0 {
+        vis.set_if_neq(Visibility::Hidden);
+        return;
+    }
+    vis.set_if_neq(Visibility::Inherited);
+    if let Some(mat) = mats.get_mut(&handle.0) {
+        mat.settings.progress = progress;
+        mat.settings.direction = direction;
+        mat.settings.time = time.elapsed_secs();
     }
 }
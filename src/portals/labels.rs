@@ -0,0 +1,294 @@
+//! Portal labels - destination name, subtitle and thumbnail over nearby doors
+//!
+//! Each door gets a small card standing above its lintel that fades in as the
+//! player walks up. The card is UI rendered into a texture and shown on a quad
+//! in the door's room, so XR sees it and walls hide it. Its UI root is also an
+//! accessibility link node for native screen readers; on the web the nearby
+//! door is read out through an `aria-live` region instead.
+
+use accesskit::{Node as Accessible, Role};
+use bevy::a11y::AccessibilityNode;
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::ui::Val::*;
+use serde::Deserialize;
+use std::f32::consts::PI;
+
+use super::{get_doors, PORTAL_HEIGHT};
+use crate::book_reader::BookTheme;
+use crate::js_bridge::announce;
+use crate::locale::{Locale, LocalizedText};
+use crate::panorama::PanoramaCamera;
+use crate::player::PlayerState;
+use crate::tour::Tour;
+use crate::world::room_center;
+
+/// Labels show within this distance (m) of a door
+const LABEL_RANGE: f32 = 3.5;
+/// Height of the label above the lintel (m)
+const LABEL_LIFT: f32 = 0.45;
+/// Label width in the world (m)
+const LABEL_WIDTH: f32 = 1.0;
+/// Card texture size (px); a thumbnail adds its own height
+const CARD_SIZE: UVec2 = UVec2::new(512, 112);
+const THUMBNAIL_SIZE: Vec2 = Vec2::new(320.0, 180.0);
+/// Opacity change per second
+const FADE_SPEED: f32 = 4.0;
+/// Card cameras render before every portal camera that may show them
+const CARD_CAMERA_ORDER: isize = -100_000;
+
+/// Shown on the doors leading into a room
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct RoomPreview {
+    /// Defaults to the room's name
+    #[serde(default)]
+    pub title: Option<LocalizedText>,
+    #[serde(default)]
+    pub subtitle: Option<LocalizedText>,
+    /// Image path
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+}
+
+#[derive(Component)]
+pub(super) struct PortalLabel {
+    room: usize,
+    door_pos: Vec2,
+    door_rot: Quat,
+    alpha: f32,
+    /// Camera rendering the card texture, only active while the label shows
+    camera: Entity,
+    /// UI root of the card, carrying the accessibility node
+    card: Entity,
+    /// Title and subtitle as read out on the web
+    announcement: String,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn setup_portal_labels(
+    mut cmd: Commands,
+    tour: Res<Tour>,
+    locale: Res<Locale>,
+    theme: Res<BookTheme>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut ordinal = 0;
+    for room in 0..tour.room_count() {
+        for door in get_doors(&tour, room) {
+            let target = &tour.rooms[door.target_room];
+            let preview = &target.preview;
+            let title = preview
+                .title
+                .as_ref()
+                .map_or(target.name.as_str(), |t| t.get(&locale))
+                .to_owned();
+            let subtitle = preview.subtitle.as_ref().map(|t| t.get(&locale).to_owned());
+            let announcement = match &subtitle {
+                Some(subtitle) => format!("{title} — {subtitle}"),
+                None => title.clone(),
+            };
+
+            let thumbnail_height = match preview.thumbnail {
+                Some(_) => THUMBNAIL_SIZE.y as u32 + 8,
+                None => 0,
+            };
+            let size = CARD_SIZE + UVec2::Y * thumbnail_height;
+            let texture = images.add(Image::new_target_texture(
+                size.x,
+                size.y,
+                TextureFormat::bevy_default(),
+            ));
+            let camera = cmd
+                .spawn((
+                    Camera2d,
+                    Camera {
+                        target: texture.clone().into(),
+                        is_active: false,
+                        order: CARD_CAMERA_ORDER - ordinal,
+                        clear_color: Color::NONE.into(),
+                        ..default()
+                    },
+                ))
+                .id();
+            ordinal += 1;
+
+            let thumbnail = preview.thumbnail.as_ref().map(|path| {
+                cmd.spawn((
+                    ImageNode::new(asset_server.load(path)),
+                    Node {
+                        width: Px(THUMBNAIL_SIZE.x),
+                        height: Px(THUMBNAIL_SIZE.y),
+                        ..default()
+                    },
+                    BorderRadius::all(Px(12.0)),
+                ))
+                .id()
+            });
+            let title_text = cmd
+                .spawn((
+                    Text::new(title.clone()),
+                    TextFont::from_font_size(36.0),
+                    TextColor(theme.text),
+                    TextShadow::default(),
+                ))
+                .id();
+            let subtitle_text = subtitle.as_ref().map(|subtitle| {
+                cmd.spawn((
+                    Text::new(subtitle.clone()),
+                    TextFont::from_font_size(26.0),
+                    TextColor(theme.muted),
+                ))
+                .id()
+            });
+
+            let mut accessible = Accessible::new(Role::Link);
+            accessible.set_label(title);
+            if let Some(subtitle) = subtitle {
+                accessible.set_description(subtitle);
+            }
+            accessible.set_hidden();
+
+            let children: Vec<Entity> = thumbnail
+                .into_iter()
+                .chain([title_text])
+                .chain(subtitle_text)
+                .collect();
+            let card = cmd
+                .spawn((
+                    Node {
+                        width: Percent(100.0),
+                        height: Percent(100.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        row_gap: Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(theme.bg),
+                    BorderRadius::all(Px(16.0)),
+                    AccessibilityNode(accessible),
+                    Pickable::IGNORE,
+                    UiTargetCamera(camera),
+                ))
+                .add_children(&children)
+                .id();
+
+            // Card bottom sits LABEL_LIFT above the lintel
+            let height = LABEL_WIDTH * size.y as f32 / size.x as f32;
+            let door_rot = Quat::from_rotation_y(door.rotation);
+            let center = room_center(room)
+                + door.local_pos
+                + door_rot * Vec3::Y * (PORTAL_HEIGHT + LABEL_LIFT + height / 2.0);
+            cmd.spawn((
+                PortalLabel {
+                    room,
+                    door_pos: Vec2::new(door.local_pos.x, door.local_pos.z),
+                    door_rot,
+                    alpha: 0.0,
+                    camera,
+                    card,
+                    announcement,
+                },
+                Mesh3d(meshes.add(Rectangle::new(LABEL_WIDTH, height))),
+                MeshMaterial3d(mats.add(StandardMaterial {
+                    base_color: Color::WHITE.with_alpha(0.0),
+                    base_color_texture: Some(texture),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                })),
+                Transform::from_translation(center).with_rotation(door_rot),
+                RenderLayers::layer(room),
+                Visibility::Hidden,
+            ));
+        }
+    }
+}
+
+/// Fade labels of nearby doors in and turn them towards the viewer's side
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub(super) fn update_portal_labels(
+    time: Res<Time>,
+    player: Res<PlayerState>,
+    camera: Query<&GlobalTransform, With<PanoramaCamera>>,
+    mut labels: Query<(
+        Entity,
+        &mut PortalLabel,
+        &mut Transform,
+        &mut Visibility,
+        &MeshMaterial3d<StandardMaterial>,
+    )>,
+    mut cameras: Query<&mut Camera>,
+    mut cards: Query<&mut AccessibilityNode>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+    mut announced: Local<Option<Entity>>,
+) {
+    let Ok(cam_tf) = camera.single() else {
+        return;
+    };
+    let step = FADE_SPEED * time.delta_secs();
+
+    for (entity, mut label, mut tr, mut vis, mat) in labels.iter_mut() {
+        let near = label.room == player.room && player.pos.distance(label.door_pos) < LABEL_RANGE;
+        let target = if near { 1.0 } else { 0.0 };
+        if label.alpha == target {
+            if target == 0.0 {
+                continue;
+            }
+        } else {
+            label.alpha = if target > label.alpha {
+                (label.alpha + step).min(target)
+            } else {
+                (label.alpha - step).max(target)
+            };
+            if let Some(mat) = mats.get_mut(&mat.0) {
+                mat.base_color = Color::WHITE.with_alpha(label.alpha);
+            }
+        }
+
+        let shown = label.alpha > 0.0;
+        if let Ok(mut card_cam) = cameras.get_mut(label.camera) {
+            if card_cam.is_active != shown {
+                card_cam.is_active = shown;
+            }
+        }
+        let Ok(mut accessible) = cards.get_mut(label.card) else {
+            continue;
+        };
+        if !shown {
+            vis.set_if_neq(Visibility::Hidden);
+            if !accessible.is_hidden() {
+                accessible.set_hidden();
+            }
+            if *announced == Some(entity) {
+                announce("");
+                *announced = None;
+            }
+            continue;
+        }
+        vis.set_if_neq(Visibility::Inherited);
+        if near && accessible.is_hidden() {
+            accessible.clear_hidden();
+        }
+        if near && *announced != Some(entity) {
+            announce(&label.announcement);
+            *announced = Some(entity);
+        }
+
+        // Readable from either side of the door
+        let front = label.door_rot * Vec3::Z;
+        let behind = front.dot(cam_tf.translation() - tr.translation) < 0.0;
+        let rot = if behind {
+            label.door_rot * Quat::from_rotation_y(PI)
+        } else {
+            label.door_rot
+        };
+        if tr.rotation != rot {
+            tr.rotation = rot;
+        }
+    }
+}
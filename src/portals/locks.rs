@@ -0,0 +1,311 @@
+//! Portal locks - doors sealed until the visitor has made some progress
+//!
+//! A door's `lock` in the tour manifest names an unlock condition. Sealed doors
+//! show the closed state of the portal shader, render no view and push the
+//! player back with a hint; the seal dissolves once the condition holds.
+
+use bevy::prelude::*;
+use bevy_kira_audio::AudioInstance;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+use super::{get_doors, PortalMaterial, PortalSurface};
+use crate::book_reader::{BookState, CHAPTER_COUNT};
+use crate::hotspots::{HotspotCard, HotspotState};
+use crate::locale::{Locale, LocalizedText};
+use crate::room_audio::RoomAudioState;
+use crate::tour::Tour;
+
+/// Seal fade per second once a door unlocks
+const UNSEAL_SPEED: f32 = 0.8;
+/// Seconds the seal flares after the player bumps into it
+const DENIED_FLASH: f32 = 0.6;
+
+/// Tour progress a door can wait for
+#[derive(Deserialize, Clone, Debug)]
+pub enum UnlockCondition {
+    /// A room's narration has been heard to the end (room index)
+    NarrationPlayed(usize),
+    /// A book chapter has been opened (1 = CHAPTER I)
+    ChapterRead(usize),
+    /// A hotspot has been activated (room index, hotspot index)
+    HotspotVisited(usize, usize),
+    /// Every condition holds
+    All(Vec<UnlockCondition>),
+    /// At least one condition holds
+    Any(Vec<UnlockCondition>),
+}
+
+/// What the visitor has done so far, as the conditions see it
+pub struct TourProgress<'a> {
+    pub audio: &'a RoomAudioState,
+    pub instances: &'a Assets<AudioInstance>,
+    pub book: &'a BookState,
+    pub hotspots: &'a HotspotState,
+}
+
+impl UnlockCondition {
+    pub fn met(&self, progress: &TourProgress) -> bool {
+        match self {
+            Self::NarrationPlayed(room) => {
+                progress.audio.narration_finished(*room, progress.instances)
+            }
+            Self::ChapterRead(chapter) => {
+                progress.book.pages_read.contains(&((*chapter).max(1) - 1))
+            }
+            Self::HotspotVisited(room, index) => {
+                progress.hotspots.visited.contains(&(*room, *index))
+            }
+            Self::All(all) => all.iter().all(|c| c.met(progress)),
+            Self::Any(any) => any.iter().any(|c| c.met(progress)),
+        }
+    }
+
+    /// Err with the reason if the condition could never hold, given which
+    /// rooms have narration and how many hotspots each room has
+    pub fn check(&self, narrations: &[bool], hotspots: &[usize]) -> Result<(), String> {
+        match self {
+            Self::NarrationPlayed(room) => match narrations.get(*room) {
+                None => Err(format!("waits for missing room {room}")),
+                Some(false) => Err(format!("waits for room {room}, which has no narration")),
+                Some(true) => Ok(()),
+            },
+            Self::ChapterRead(chapter) if !(1..=CHAPTER_COUNT).contains(chapter) => Err(format!(
+                "waits for chapter {chapter}, the book has 1 to {CHAPTER_COUNT}"
+            )),
+            Self::ChapterRead(_) => Ok(()),
+            Self::HotspotVisited(room, index) => match hotspots.get(*room) {
+                None => Err(format!("waits for missing room {room}")),
+                Some(count) if index >= count => {
+                    Err(format!("waits for missing hotspot {index} of room {room}"))
+                }
+                Some(_) => Ok(()),
+            },
+            Self::All(all) => all.iter().try_for_each(|c| c.check(narrations, hotspots)),
+            // One branch that can hold is enough
+            Self::Any(any) => any
+                .iter()
+                .map(|c| c.check(narrations, hotspots))
+                .reduce(Result::or)
+                .unwrap_or_else(|| Err("waits for an empty Any".into())),
+        }
+    }
+}
+
+/// Door lock entry in the tour manifest
+#[derive(Deserialize, Clone, Debug)]
+pub struct DoorLock {
+    pub when: UnlockCondition,
+    /// Shown when the player walks into the sealed door
+    #[serde(default)]
+    pub hint: Option<LocalizedText>,
+}
+
+impl DoorLock {
+    /// Say what opens the door, unless a card is already up
+    pub fn show_hint(&self, card: &mut HotspotCard, locale: &Locale) {
+        if let Some(hint) = &self.hint {
+            if !card.open {
+                card.show(sealed_title(locale), hint.get(locale), None);
+            }
+        }
+    }
+}
+
+/// Hint card title in the visitor's language
+fn sealed_title(locale: &Locale) -> &'static str {
+    match locale.language() {
+        "pt" => "Portal selado",
+        "es" => "Portal sellado",
+        "fr" => "Portail scellé",
+        "de" => "Versiegeltes Portal",
+        "it" => "Portale sigillato",
+        "zh" => "封印之门",
+        "ja" => "封印された門",
+        _ => "Sealed portal",
+    }
+}
+
+/// Doors still sealed, by (room, door index)
+#[derive(Resource, Default)]
+pub struct PortalLocks {
+    sealed: HashSet<(usize, usize)>,
+    /// Player walked into a sealed door: (room, door index, seconds since)
+    denied: Option<(usize, usize, f32)>,
+}
+
+impl PortalLocks {
+    pub fn is_sealed(&self, room: usize, door: usize) -> bool {
+        self.sealed.contains(&(room, door))
+    }
+
+    /// Flare a sealed door's seal
+    pub fn deny(&mut self, room: usize, door: usize) {
+        self.denied = Some((room, door, 0.0));
+    }
+
+    /// Sealed door that keeps map travel from `from` out of `to`, if any.
+    /// Rooms the doors don't link at all stay open unless every door into
+    /// them is sealed.
+    pub fn barrier(&self, tour: &Tour, from: usize, to: usize) -> Option<(usize, usize)> {
+        let open = self.rooms_reachable(tour, from, true);
+        if open.contains(&to) {
+            return None;
+        }
+        let doors_into = |room: usize| {
+            get_doors(tour, room)
+                .iter()
+                .filter(move |door| door.target_room == to)
+                .map(move |door| (room, door.door_index))
+        };
+        if self.rooms_reachable(tour, from, false).contains(&to) {
+            // The seal between here and there: into `to` if one is in reach,
+            // else the first one out of the open rooms
+            let mut frontier: Vec<_> = open.into_iter().collect();
+            frontier.sort_unstable();
+            let sealed_out = |room: usize| {
+                get_doors(tour, room)
+                    .iter()
+                    .map(move |door| (room, door.door_index))
+                    .filter(|&(room, door)| self.is_sealed(room, door))
+            };
+            return frontier
+                .iter()
+                .find_map(|&room| doors_into(room).find(|&(r, d)| self.is_sealed(r, d)))
+                .or_else(|| frontier.iter().find_map(|&room| sealed_out(room).next()));
+        }
+        let incoming: Vec<_> = (0..tour.room_count()).flat_map(doors_into).collect();
+        incoming.first().copied().filter(|_| {
+            incoming
+                .iter()
+                .all(|&(room, door)| self.is_sealed(room, door))
+        })
+    }
+
+    /// Every room `barrier` would keep map travel from `from` out of, in one
+    /// pass over the doors
+    pub fn barred_rooms(&self, tour: &Tour, from: usize) -> HashSet<usize> {
+        let open = self.rooms_reachable(tour, from, true);
+        let linked = self.rooms_reachable(tour, from, false);
+        // Per room: (has a door leading in, every door in is sealed)
+        let mut incoming = vec![(false, true); tour.room_count()];
+        for room in 0..tour.room_count() {
+            for door in get_doors(tour, room) {
+                if let Some(into) = incoming.get_mut(door.target_room) {
+                    into.0 = true;
+                    into.1 &= self.is_sealed(room, door.door_index);
+                }
+            }
+        }
+        (0..tour.room_count())
+            .filter(|room| !open.contains(room))
+            .filter(|room| linked.contains(room) || incoming[*room] == (true, true))
+            .collect()
+    }
+
+    /// Rooms reachable from `from` through doors, optionally only open ones
+    fn rooms_reachable(&self, tour: &Tour, from: usize, open_only: bool) -> HashSet<usize> {
+        let mut seen = HashSet::from([from]);
+        let mut queue = vec![from];
+        while let Some(room) = queue.pop() {
+            for door in get_doors(tour, room) {
+                if open_only && self.is_sealed(room, door.door_index) {
+                    continue;
+                }
+                if door.target_room < tour.room_count() && seen.insert(door.target_room) {
+                    queue.push(door.target_room);
+                }
+            }
+        }
+        seen
+    }
+}
+
+pub(super) fn setup_portal_locks(mut locks: ResMut<PortalLocks>, tour: Res<Tour>) {
+    locks.denied = None;
+    locks.sealed = (0..tour.room_count())
+        .flat_map(|room| {
+            get_doors(&tour, room)
+                .iter()
+                .filter(|door| door.lock.is_some())
+                .map(move |door| (room, door.door_index))
+        })
+        .collect();
+    if !locks.sealed.is_empty() {
+        info!("🔒 {} portals sealed", locks.sealed.len());
+    }
+}
+
+/// Unseal doors whose condition now holds
+pub(super) fn evaluate_portal_locks(
+    tour: Res<Tour>,
+    audio: Res<RoomAudioState>,
+    instances: Res<Assets<AudioInstance>>,
+    book: Res<BookState>,
+    hotspots: Res<HotspotState>,
+    mut locks: ResMut<PortalLocks>,
+) {
+    if locks.sealed.is_empty() {
+        return;
+    }
+    let progress = TourProgress {
+        audio: &audio,
+        instances: &instances,
+        book: &book,
+        hotspots: &hotspots,
+    };
+    let opened: Vec<(usize, usize)> = locks
+        .sealed
+        .iter()
+        .copied()
+        .filter(|&(room, door)| {
+            get_doors(&tour, room)
+                .get(door)
+                .and_then(|door| door.lock.as_ref())
+                .is_none_or(|lock| lock.when.met(&progress))
+        })
+        .collect();
+    for (room, door) in opened {
+        locks.sealed.remove(&(room, door));
+        info!("🔓 Portal {} of room {} unsealed", door + 1, room + 1);
+    }
+}
+
+/// Ease each surface's seal towards its door's state and play the bump flare
+pub(super) fn animate_portal_seals(
+    time: Res<Time>,
+    mut locks: ResMut<PortalLocks>,
+    mut portal_mats: ResMut<Assets<PortalMaterial>>,
+    surfaces: Query<(&PortalSurface, &MeshMaterial3d<PortalMaterial>)>,
+) {
+    let dt = time.delta_secs();
+    if let Some((_, _, since)) = &mut locks.denied {
+        *since += dt;
+    }
+    let denied = locks.denied.filter(|(.., since)| *since < DENIED_FLASH);
+    if denied.is_none() && locks.denied.is_some() {
+        locks.denied = None;
+    }
+
+    for (surface, handle) in surfaces.iter() {
+        let target = if locks.is_sealed(surface.room, surface.door) {
+            1.0
+        } else {
+            0.0
+        };
+        let flash = denied
+            .filter(|(room, door, _)| (*room, *door) == (surface.room, surface.door))
+            .map_or(0.0, |(.., since)| 1.0 - since / DENIED_FLASH);
+
+        // Seal instantly, unseal slowly
+        if let Some(mat) = portal_mats.get_mut(&handle.0) {
+            let locked = mat.settings.locked;
+            mat.settings.locked = if target > locked {
+                target
+            } else {
+                (locked - UNSEAL_SPEED * dt).max(target)
+            };
+            mat.settings.denied = flash;
+        }
+    }
+}
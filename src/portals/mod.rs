@@ -1,14 +1,36 @@
 //! Portals module - Portal doors, crossing logic, render textures
 
+mod labels;
+mod locks;
+mod projection;
+mod style;
+
+pub use labels::RoomPreview;
+use labels::{setup_portal_labels, update_portal_labels};
+pub use locks::*;
+pub use projection::*;
+pub use style::*;
+
+use bevy::camera::primitives::{Aabb, Frustum};
 use bevy::camera::visibility::RenderLayers;
+use bevy::camera::RenderTarget;
 use bevy::prelude::*;
-use bevy::render::render_resource::{AsBindGroup, TextureFormat};
+use bevy::render::render_resource::{AsBindGroup, Extent3d, TextureFormat};
 use bevy::shader::ShaderRef;
 
+use crate::camera::CameraState;
+use crate::hotspots::HotspotCard;
+use crate::input::InputEvent;
+use crate::locale::Locale;
 use crate::panorama::PanoramaCamera;
+use crate::performance::{QualityChanged, QualityLevel, QualitySettings};
 use crate::player::PlayerState;
-use crate::world::{room_center, TOTAL_ROOMS};
+use crate::portal_transition::PortalTransitionState;
+use crate::tour::Tour;
+use crate::world::{room_center, room_view_layers, Eye, EYE_LAYER_BASE, MAX_ROOMS};
 use crate::GameState;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
 
 pub struct PortalsPlugin;
 
@@ -16,14 +38,26 @@ impl Plugin for PortalsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(MaterialPlugin::<PortalMaterial>::default())
             .insert_resource(PortalState::default())
-            .add_systems(OnEnter(GameState::Viewing), setup_portal_frames)
+            .init_resource::<PortalLocks>()
+            .add_message::<TravelToRoom>()
+            .add_message::<PortalCrossed>()
+            .add_systems(
+                OnEnter(GameState::Viewing),
+                (setup_portal_frames, setup_portal_locks, setup_portal_labels),
+            )
             .add_systems(
                 Update,
                 (
                     spawn_portal_views,
+                    evaluate_portal_locks,
+                    animate_portal_seals,
+                    apply_portal_quality,
                     sync_portal_cameras,
                     update_portal_time,
                     portal_crossing,
+                    next_room_input,
+                    travel_to_room,
+                    update_portal_labels,
                 )
                     .chain()
                     .run_if(in_state(GameState::Viewing)),
@@ -34,38 +68,95 @@ impl Plugin for PortalsPlugin {
 pub const PORTAL_WIDTH: f32 = 1.0;
 pub const PORTAL_HEIGHT: f32 = 2.2;
 const FRAME_DEPTH: f32 = 0.15;
+/// Portal render target width at `QualityLevel::High` (height follows the door)
+const PORTAL_TEXTURE_WIDTH: f32 = 512.0;
+/// Slack (m) around a door when testing it against last frame's view frustum
+const CULL_MARGIN: f32 = 0.5;
+/// Deepest portal-in-portal recursion any quality level asks for
+pub const MAX_PORTAL_DEPTH: usize = 2;
+/// First render layer used for portal surfaces (above the per-eye sky layers)
+const PORTAL_LAYER_BASE: usize = EYE_LAYER_BASE + 2 * MAX_ROOMS;
+
+/// The player walked through a door
+#[derive(Message, Clone, Copy, Debug)]
+pub struct PortalCrossed {
+    pub from_room: usize,
+    pub to_room: usize,
+}
+
+/// Take the player to a room's origin behind the crossing transition
+/// (hotspot jumps, map travel, next room)
+#[derive(Message, Clone, Copy, Debug)]
+pub struct TravelToRoom {
+    pub room: usize,
+}
 
 #[derive(Resource, Default)]
 struct PortalState {
     spawned: bool,
     frames_waited: u32,
+    /// Room a jump cuts to once the transition covers the view
+    travel: Option<usize>,
 }
 
-#[derive(Clone, Copy)]
+/// One edge of the room graph - a door and the door it exits through
+#[derive(Clone)]
 pub struct DoorConfig {
+    pub id: String,
     pub local_pos: Vec3,
     pub rotation: f32,
     pub target_room: usize,
-    pub door_index: usize, // 0 = left door, 1 = right door
+    pub target_door: usize, // Index into the target room's door list
+    pub door_index: usize,  // Position in the room's door list
+    pub lock: Option<DoorLock>,
 }
 
-pub fn get_doors(room: usize) -> [DoorConfig; 2] {
-    let prev = if room == 0 { TOTAL_ROOMS - 1 } else { room - 1 };
-    let next = (room + 1) % TOTAL_ROOMS;
-    [
-        DoorConfig {
-            local_pos: Vec3::new(-5.0, 0.0, -5.0),
-            rotation: 0.3,
-            target_room: prev,
-            door_index: 0,
-        },
-        DoorConfig {
-            local_pos: Vec3::new(5.0, 0.0, -5.0),
-            rotation: -0.3,
-            target_room: next,
-            door_index: 1,
-        },
-    ]
+/// Doors declared for a room in the tour manifest
+pub fn get_doors(tour: &Tour, room: usize) -> &[DoorConfig] {
+    tour.rooms
+        .get(room)
+        .map(|r| r.doors.as_slice())
+        .unwrap_or_default()
+}
+
+/// Render layer for the portal surfaces in `room` seen at recursion `level`
+/// (1 = by the player, 2 = through one portal, ...)
+pub fn portal_layer(room: usize, level: usize) -> usize {
+    PORTAL_LAYER_BASE + room * (MAX_PORTAL_DEPTH + 2) + level - 1
+}
+
+/// Layers a portal camera of `level` looking into `room` renders: the room,
+/// and the door surfaces one level deeper
+fn portal_view_layers(room: usize, level: usize) -> RenderLayers {
+    room_view_layers(room, Eye::Left)
+        .without(portal_layer(room, 1))
+        .with(portal_layer(room, level + 1))
+}
+
+/// Whether a surface at `level` shows the tint instead of a view
+fn portal_fallback(level: usize, depth: usize) -> f32 {
+    if level > depth + 1 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Render target size for a portal at this quality
+fn portal_target_size(level: QualityLevel) -> Extent3d {
+    let width = (PORTAL_TEXTURE_WIDTH * level.portal_resolution_scale()).max(64.0);
+    Extent3d {
+        width: width as u32,
+        height: (width * PORTAL_HEIGHT / PORTAL_WIDTH) as u32,
+        depth_or_array_layers: 1,
+    }
+}
+
+/// World transform of a door's portal quad (centred, facing the door's front)
+fn door_surface(room: usize, door: &DoorConfig) -> Transform {
+    let rot = Quat::from_rotation_y(door.rotation);
+    let pos = room_center(room) + door.local_pos + rot * Vec3::new(0.0, PORTAL_HEIGHT / 2.0, 0.08);
+    Transform::from_translation(pos).with_rotation(rot)
 }
 
 /// Portal material with liquid effect
@@ -80,10 +171,28 @@ pub struct PortalMaterial {
 
 #[derive(Debug, Clone, Copy, Default, bevy::render::render_resource::ShaderType)]
 pub struct PortalSettings {
+    pub tint: LinearRgba,
     pub time: f32,
-    pub _pad1: f32,
-    pub _pad2: f32,
-    pub _pad3: f32,
+    pub fallback: f32, // 1 = past the recursion depth, show a tint instead of the view
+    pub shape: f32,    // `PortalShape::shader_index`
+    pub distortion: f32,
+    pub edge_glow: f32,
+    pub aspect: f32, // door width / height
+    pub locked: f32, // 1 = sealed, fading to 0 as the door unlocks
+    pub denied: f32, // flare when the player walks into a sealed door
+}
+
+impl PortalSettings {
+    fn new(style: &PortalStyle) -> Self {
+        Self {
+            tint: style.effect.tint.to_linear(),
+            shape: style.shape.shader_index(),
+            distortion: style.effect.distortion,
+            edge_glow: style.effect.edge_glow,
+            aspect: PORTAL_WIDTH / PORTAL_HEIGHT,
+            ..default()
+        }
+    }
 }
 
 impl Material for PortalMaterial {
@@ -99,6 +208,7 @@ impl Material for PortalMaterial {
 pub struct PortalDoor {
     pub room: usize,
     pub target_room: usize,
+    pub target_door: usize,
     pub door_index: usize,
     pub local_pos: Vec2,
     pub rotation: f32,
@@ -107,8 +217,21 @@ pub struct PortalDoor {
 #[derive(Component)]
 struct PortalCamera {
     source_room: usize,
+    source_door: usize,
     target_room: usize,
-    door_rotation: f32,
+    /// Recursion level this camera renders for (1 = seen by the player)
+    level: usize,
+    /// Portal quad looked through, and the quad it opens out of
+    source: Transform,
+    exit: Transform,
+}
+
+/// A door's portal quad at one recursion level
+#[derive(Component)]
+struct PortalSurface {
+    room: usize,
+    door: usize, // index into the room's door list
+    level: usize,
 }
 
 #[derive(Component)]
@@ -119,103 +242,47 @@ fn setup_portal_frames(
     mut meshes: ResMut<Assets<Mesh>>,
     mut mats: ResMut<Assets<StandardMaterial>>,
     mut state: ResMut<PortalState>,
+    tour: Res<Tour>,
 ) {
     state.spawned = false;
     state.frames_waited = 0;
+    state.travel = None;
+
+    // Meshes and materials shared by every door with the same shape / kit
+    let mut kits = HashMap::new();
+    let mut frames = HashMap::new();
+
+    for room in 0..tour.room_count() {
+        let style = tour.rooms[room].portal_style;
+        let Some([body_mat, trim_mat, glow_mat]) = kits
+            .entry(style.frame)
+            .or_insert_with(|| {
+                style
+                    .frame
+                    .materials()
+                    .map(|kit| [kit.body, kit.trim, kit.glow].map(|mat| mats.add(mat)))
+            })
+            .clone()
+        else {
+            continue;
+        };
+        let [body, trim, glow] = frames
+            .entry(style.shape)
+            .or_insert_with(|| {
+                let parts = frame_meshes(style.shape);
+                [parts.body, parts.trim, parts.glow].map(|mesh| meshes.add(mesh))
+            })
+            .clone();
 
-    // Chinese red lacquered wood frame
-    let frame_mat = mats.add(StandardMaterial {
-        base_color: Color::srgb(0.25, 0.02, 0.02),
-        perceptual_roughness: 0.4,
-        metallic: 0.05,
-        ..default()
-    });
-    // Gold trim accents
-    let gold_mat = mats.add(StandardMaterial {
-        base_color: Color::srgb(1.0, 0.84, 0.0),
-        metallic: 0.95,
-        perceptual_roughness: 0.15,
-        emissive: LinearRgba::new(0.4, 0.28, 0.0, 1.0),
-        ..default()
-    });
-    // Red energy glow
-    let glow_mat = mats.add(StandardMaterial {
-        base_color: Color::srgba(0.86, 0.08, 0.24, 0.8),
-        emissive: LinearRgba::new(3.0, 0.3, 0.5, 1.0),
-        unlit: true,
-        alpha_mode: AlphaMode::Blend,
-        ..default()
-    });
-
-    let pillar = meshes.add(Cuboid::new(0.12, PORTAL_HEIGHT + 0.3, FRAME_DEPTH));
-    let lintel = meshes.add(Cuboid::new(PORTAL_WIDTH + 0.3, 0.15, FRAME_DEPTH));
-    let trim_v = meshes.add(Cuboid::new(0.04, PORTAL_HEIGHT + 0.2, FRAME_DEPTH + 0.02));
-    let trim_h = meshes.add(Cuboid::new(PORTAL_WIDTH + 0.35, 0.04, FRAME_DEPTH + 0.02));
-    let glow_ring = meshes.add(Cuboid::new(0.02, PORTAL_HEIGHT - 0.1, 0.02));
-
-    for room in 0..TOTAL_ROOMS {
         let center = room_center(room);
-        for door in get_doors(room) {
-            let world_pos = center + door.local_pos;
-            let rot = Quat::from_rotation_y(door.rotation);
-            let half_w = PORTAL_WIDTH / 2.0 + 0.06;
-
-            // Pillars
-            for x in [-half_w, half_w] {
-                cmd.spawn((
-                    Mesh3d(pillar.clone()),
-                    MeshMaterial3d(frame_mat.clone()),
-                    Transform::from_translation(
-                        world_pos + rot * Vec3::new(x, PORTAL_HEIGHT / 2.0, 0.0),
-                    )
-                    .with_rotation(rot),
-                    RenderLayers::layer(room),
-                    PortalFrame,
-                ));
-            }
-            // Lintel
-            cmd.spawn((
-                Mesh3d(lintel.clone()),
-                MeshMaterial3d(frame_mat.clone()),
-                Transform::from_translation(
-                    world_pos + rot * Vec3::new(0.0, PORTAL_HEIGHT + 0.08, 0.0),
-                )
-                .with_rotation(rot),
-                RenderLayers::layer(room),
-                PortalFrame,
-            ));
-            // Gold trim
-            for x in [-half_w - 0.01, half_w + 0.01] {
-                cmd.spawn((
-                    Mesh3d(trim_v.clone()),
-                    MeshMaterial3d(gold_mat.clone()),
-                    Transform::from_translation(
-                        world_pos + rot * Vec3::new(x, PORTAL_HEIGHT / 2.0, 0.02),
-                    )
-                    .with_rotation(rot),
-                    RenderLayers::layer(room),
-                    PortalFrame,
-                ));
-            }
-            cmd.spawn((
-                Mesh3d(trim_h.clone()),
-                MeshMaterial3d(gold_mat.clone()),
-                Transform::from_translation(
-                    world_pos + rot * Vec3::new(0.0, PORTAL_HEIGHT + 0.1, 0.02),
-                )
-                .with_rotation(rot),
-                RenderLayers::layer(room),
-                PortalFrame,
-            ));
-            // Glow
-            for x in [-half_w + 0.08, half_w - 0.08] {
+        for door in get_doors(&tour, room) {
+            let transform = Transform::from_translation(center + door.local_pos)
+                .with_rotation(Quat::from_rotation_y(door.rotation));
+            for (mesh, mat) in [(&body, &body_mat), (&trim, &trim_mat), (&glow, &glow_mat)] {
                 cmd.spawn((
-                    Mesh3d(glow_ring.clone()),
-                    MeshMaterial3d(glow_mat.clone()),
-                    Transform::from_translation(
-                        world_pos + rot * Vec3::new(x, PORTAL_HEIGHT / 2.0, 0.06),
-                    )
-                    .with_rotation(rot),
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(mat.clone()),
+                    transform,
                     RenderLayers::layer(room),
                     PortalFrame,
                 ));
@@ -225,12 +292,71 @@ fn setup_portal_frames(
     info!("🚪 Portal frames created");
 }
 
+/// Every door of the tour in a fixed order, with its room
+fn tour_doors(tour: &Tour) -> impl Iterator<Item = (usize, &DoorConfig)> {
+    (0..tour.room_count())
+        .flat_map(move |room| get_doors(tour, room).iter().map(move |door| (room, door)))
+}
+
+/// Portal camera of `door` at `level`, rendering into a new target
+fn spawn_portal_camera(
+    cmd: &mut Commands,
+    images: &mut Assets<Image>,
+    tour: &Tour,
+    (room, door): (usize, &DoorConfig),
+    level: usize,
+    size: Extent3d,
+    ordinal: usize,
+) -> Handle<Image> {
+    // Rooms without doors open out of their centre
+    let exit = get_doors(tour, door.target_room)
+        .get(door.target_door)
+        .map(|exit| door_surface(door.target_room, exit))
+        .unwrap_or_else(|| {
+            Transform::from_translation(
+                room_center(door.target_room) + Vec3::Y * PORTAL_HEIGHT / 2.0,
+            )
+        });
+    let rt = images.add(Image::new_target_texture(
+        size.width,
+        size.height,
+        TextureFormat::bevy_default(),
+    ));
+    // Deeper levels render first so shallower views can show them
+    cmd.spawn((
+        Camera3d::default(),
+        Camera {
+            target: rt.clone().into(),
+            is_active: false,
+            order: -(level as isize * 1000) - ordinal as isize,
+            clear_color: Color::srgb(0.01, 0.005, 0.02).into(),
+            ..default()
+        },
+        Projection::custom(PortalProjection::default()),
+        exit,
+        portal_view_layers(door.target_room, level),
+        PortalCamera {
+            source_room: room,
+            source_door: door.door_index,
+            target_room: door.target_room,
+            level,
+            source: door_surface(room, door),
+            exit,
+        },
+    ));
+    rt
+}
+
+#[allow(clippy::too_many_arguments)]
 fn spawn_portal_views(
     mut cmd: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut images: ResMut<Assets<Image>>,
     mut portal_mats: ResMut<Assets<PortalMaterial>>,
     mut state: ResMut<PortalState>,
+    tour: Res<Tour>,
+    quality: Res<QualitySettings>,
+    locks: Res<PortalLocks>,
     cam_q: Query<Entity, With<PanoramaCamera>>,
 ) {
     if state.spawned {
@@ -245,84 +371,241 @@ fn spawn_portal_views(
     }
 
     state.spawned = true;
-    let portal_mesh = meshes.add(Rectangle::new(PORTAL_WIDTH, PORTAL_HEIGHT));
-
-    for room in 0..TOTAL_ROOMS {
-        let center = room_center(room);
-        for (idx, door) in get_doors(room).iter().enumerate() {
-            let world_pos = center + door.local_pos;
-            let rot = Quat::from_rotation_y(door.rotation);
-            let portal_pos = world_pos + rot * Vec3::new(0.0, PORTAL_HEIGHT / 2.0, 0.08);
-
-            let rt = images.add(Image::new_target_texture(
-                512,
-                1024,
-                TextureFormat::bevy_default(),
-            ));
-
-            // Portal camera - positioned at target room
-            let target_center = room_center(door.target_room);
-            cmd.spawn((
-                Camera3d::default(),
-                Camera {
-                    target: rt.clone().into(),
-                    order: -10 - (room * 2 + idx) as isize,
-                    clear_color: Color::srgb(0.01, 0.005, 0.02).into(),
-                    ..default()
-                },
-                Transform::from_translation(target_center)
-                    .looking_at(target_center + Vec3::NEG_Z, Vec3::Y),
-                RenderLayers::layer(door.target_room),
-                PortalCamera {
-                    source_room: room,
-                    target_room: door.target_room,
-                    door_rotation: door.rotation,
-                },
-            ));
+    let mut portal_meshes = HashMap::new();
+    let size = portal_target_size(quality.level);
+    let depth = quality.level.portal_depth();
+    let mut portal_count = 0;
+
+    for (room, door) in tour_doors(&tour) {
+        let style = tour.rooms[room].portal_style;
+        let portal_mesh = portal_meshes
+            .entry(style.shape)
+            .or_insert_with(|| meshes.add(surface_mesh(style.shape)))
+            .clone();
+        let source = door_surface(room, door);
+
+        // One camera per recursion level the quality renders
+        let targets: Vec<Handle<Image>> = (1..=depth + 1)
+            .map(|level| {
+                spawn_portal_camera(
+                    &mut cmd,
+                    &mut images,
+                    &tour,
+                    (room, door),
+                    level,
+                    size,
+                    portal_count,
+                )
+            })
+            .collect();
 
-            // Portal surface
-            cmd.spawn((
+        // One surface per level, each on the layer the views of that level
+        // render; levels past the depth show the fallback tint
+        let surface = |level: usize| {
+            (
                 Mesh3d(portal_mesh.clone()),
-                MeshMaterial3d(portal_mats.add(PortalMaterial {
-                    settings: PortalSettings::default(),
-                    view_texture: rt,
-                })),
-                Transform::from_translation(portal_pos).with_rotation(rot),
-                RenderLayers::layer(room),
-                PortalDoor {
+                RenderLayers::layer(portal_layer(room, level)),
+                PortalSurface {
                     room,
-                    target_room: door.target_room,
-                    door_index: door.door_index,
-                    local_pos: Vec2::new(door.local_pos.x, door.local_pos.z),
-                    rotation: door.rotation,
+                    door: door.door_index,
+                    level,
+                },
+            )
+        };
+        let material = |level: usize, mats: &mut Assets<PortalMaterial>| {
+            MeshMaterial3d(mats.add(PortalMaterial {
+                settings: PortalSettings {
+                    fallback: portal_fallback(level, depth),
+                    locked: if locks.is_sealed(room, door.door_index) {
+                        1.0
+                    } else {
+                        0.0
+                    },
+                    ..PortalSettings::new(&style)
                 },
+                view_texture: targets.get(level - 1).cloned().unwrap_or_default(),
+            }))
+        };
+        let mut entity = cmd.spawn((
+            surface(1),
+            material(1, &mut portal_mats),
+            source,
+            PortalDoor {
+                room,
+                target_room: door.target_room,
+                target_door: door.target_door,
+                door_index: door.door_index,
+                local_pos: Vec2::new(door.local_pos.x, door.local_pos.z),
+                rotation: door.rotation,
+            },
+        ));
+        for level in 2..=MAX_PORTAL_DEPTH + 2 {
+            entity.with_child((
+                surface(level),
+                material(level, &mut portal_mats),
+                Transform::default(),
             ));
         }
+        portal_count += 1;
     }
-    info!("🌀 {} portals spawned", TOTAL_ROOMS * 2);
+    info!(
+        "🌀 {} portals spawned ({}x{}, recursion depth {})",
+        portal_count, size.width, size.height, depth
+    );
 }
 
-fn sync_portal_cameras(
-    player: Res<PlayerState>,
-    main_cam: Query<&Transform, (With<PanoramaCamera>, Without<PortalCamera>)>,
-    mut portal_cams: Query<(&mut Transform, &PortalCamera), Without<PanoramaCamera>>,
+/// Resize portal targets, add or drop the cameras of recursion levels the new
+/// depth gains or loses, and move the fallback tint to it
+#[allow(clippy::too_many_arguments)]
+fn apply_portal_quality(
+    mut cmd: Commands,
+    mut events: MessageReader<QualityChanged>,
+    mut images: ResMut<Assets<Image>>,
+    mut portal_mats: ResMut<Assets<PortalMaterial>>,
+    state: Res<PortalState>,
+    tour: Res<Tour>,
+    cams: Query<(Entity, &Camera, &PortalCamera)>,
+    surfaces: Query<(&PortalSurface, &MeshMaterial3d<PortalMaterial>)>,
 ) {
-    let Ok(main_tf) = main_cam.single() else {
+    let Some(level) = events.read().last().map(|ev| ev.new) else {
         return;
     };
-    let (yaw, pitch, _) = main_tf.rotation.to_euler(EulerRot::YXZ);
+    // Not spawned yet - spawning reads the current quality
+    if !state.spawned {
+        return;
+    }
+    let size = portal_target_size(level);
+    let depth = level.portal_depth();
 
-    for (mut cam_tf, portal) in portal_cams.iter_mut() {
-        if portal.source_room != player.room {
+    let mut present = HashSet::new();
+    for (entity, camera, portal) in cams.iter() {
+        if portal.level > depth + 1 {
+            cmd.entity(entity).despawn();
             continue;
         }
+        present.insert((portal.source_room, portal.source_door, portal.level));
+        if let RenderTarget::Image(target) = &camera.target {
+            if let Some(image) = images.get_mut(&target.handle) {
+                image.resize(size);
+            }
+        }
+    }
 
-        let target_center = room_center(portal.target_room);
-        cam_tf.translation = target_center + Vec3::Y * 1.7;
+    let mut added = HashMap::new();
+    for (ordinal, (room, door)) in tour_doors(&tour).enumerate() {
+        for level in 1..=depth + 1 {
+            if !present.contains(&(room, door.door_index, level)) {
+                let rt = spawn_portal_camera(
+                    &mut cmd,
+                    &mut images,
+                    &tour,
+                    (room, door),
+                    level,
+                    size,
+                    ordinal,
+                );
+                added.insert((room, door.door_index, level), rt);
+            }
+        }
+    }
 
-        // Mirror the view through the portal
-        let mirrored_yaw = yaw + std::f32::consts::PI + portal.door_rotation * 2.0;
-        cam_tf.rotation = Quat::from_euler(EulerRot::YXZ, mirrored_yaw, pitch, 0.0);
+    // Dropped levels let go of their targets so the textures are freed
+    for (surface, handle) in surfaces.iter() {
+        if let Some(mat) = portal_mats.get_mut(&handle.0) {
+            mat.settings.fallback = portal_fallback(surface.level, depth);
+            if surface.level > depth + 1 {
+                mat.view_texture = Handle::default();
+            } else if let Some(rt) = added.get(&(surface.room, surface.door, surface.level)) {
+                mat.view_texture = rt.clone();
+            }
+        }
+    }
+    info!(
+        "🔧 Portal targets resized: {}x{}, recursion depth {} (+{} views)",
+        size.width,
+        size.height,
+        depth,
+        added.len()
+    );
+}
+
+/// Place each portal camera where the viewing eye would be relative to the
+/// exit door, and fit its frustum to the door as seen from there. Level 1
+/// looks through the doors of the player's room; each further level looks
+/// through the doors seen by the level before. Only doors that face a viewer
+/// and are in its view get rendered.
+#[allow(clippy::type_complexity)]
+fn sync_portal_cameras(
+    player: Res<PlayerState>,
+    quality: Res<QualitySettings>,
+    locks: Res<PortalLocks>,
+    main_cam: Query<(&Transform, &Frustum), (With<PanoramaCamera>, Without<PortalCamera>)>,
+    mut portal_cams: Query<
+        (
+            &mut Camera,
+            &mut Transform,
+            &mut Projection,
+            &Frustum,
+            &PortalCamera,
+        ),
+        Without<PanoramaCamera>,
+    >,
+) {
+    let Ok((main_tf, view)) = main_cam.single() else {
+        return;
+    };
+    let door_bounds = Aabb::from_min_max(
+        Vec3::new(-PORTAL_WIDTH / 2.0, -PORTAL_HEIGHT / 2.0, 0.0) - CULL_MARGIN,
+        Vec3::new(PORTAL_WIDTH / 2.0, PORTAL_HEIGHT / 2.0, 0.0) + CULL_MARGIN,
+    );
+    let max_level = quality.level.portal_depth() + 1;
+
+    // Room each viewer looks into, its eye and its view frustum
+    let mut viewers = vec![(player.room, main_tf.translation, *view)];
+    for level in 1..=MAX_PORTAL_DEPTH + 1 {
+        let mut next = Vec::new();
+        for (mut camera, mut cam_tf, mut projection, cam_view, portal) in portal_cams.iter_mut() {
+            if portal.level != level {
+                continue;
+            }
+
+            // Eye in the source door's frame; behind the door the quad isn't
+            // drawn, and a sealed door shows no view
+            let door = portal.source.compute_affine();
+            let open =
+                level <= max_level && !locks.is_sealed(portal.source_room, portal.source_door);
+            let seen = viewers
+                .iter()
+                .filter(|(room, ..)| *room == portal.source_room && open)
+                .find_map(|(_, eye, view)| {
+                    let eye = door.inverse().transform_point3(*eye);
+                    PortalProjection::try_from_eye(eye)
+                        .filter(|_| view.intersects_obb(&door_bounds, &door, true, false))
+                        .map(|frustum| (eye, frustum))
+                });
+
+            let active = seen.is_some();
+            if camera.is_active != active {
+                camera.is_active = active;
+            }
+            let Some((eye, frustum)) = seen else {
+                continue;
+            };
+
+            // Walking in through the front comes out of the exit's front, so the
+            // exit frame is turned half a turn; the camera keeps the door's axes
+            let through = portal.exit.rotation * Quat::from_rotation_y(PI);
+            *cam_tf = Transform::from_translation(portal.exit.translation + through * eye)
+                .with_rotation(through);
+
+            if let Projection::Custom(custom) = &mut *projection {
+                if let Some(current) = custom.get_mut::<PortalProjection>() {
+                    *current = frustum;
+                }
+            }
+            next.push((portal.target_room, cam_tf.translation, *cam_view));
+        }
+        viewers = next;
     }
 }
 
@@ -339,11 +622,21 @@ fn update_portal_time(
     }
 }
 
+/// Walking through a door carries the player over to the exit door with the
+/// same mapping the portal cameras use, so position, heading and walking
+/// direction continue from what the portal showed
+#[allow(clippy::too_many_arguments)]
 fn portal_crossing(
     mut cmd: Commands,
+    mut crossed: MessageWriter<PortalCrossed>,
     mut player: ResMut<PlayerState>,
+    mut camera: ResMut<CameraState>,
+    mut locks: ResMut<PortalLocks>,
+    mut card: ResMut<HotspotCard>,
+    locale: Res<Locale>,
     mut cam_q: Query<(Entity, &mut Transform, Option<&mut RenderLayers>), With<PanoramaCamera>>,
     portals: Query<&PortalDoor>,
+    tour: Res<Tour>,
 ) {
     let Ok((cam_entity, mut cam, layers_opt)) = cam_q.single_mut() else {
         return;
@@ -358,47 +651,161 @@ fn portal_crossing(
         if dist > 1.5 {
             continue;
         }
-
-        // Door normal
-        let normal = Vec2::new(portal.rotation.sin(), -portal.rotation.cos());
-        let prev_dot = (player.prev_pos - portal.local_pos).dot(normal);
-        let curr_dot = (player.pos - portal.local_pos).dot(normal);
-
-        // Check crossing (sign change)
-        if prev_dot * curr_dot >= 0.0 {
+        let Some(entry) = get_doors(&tour, portal.room).get(portal.door_index) else {
             continue;
-        }
-
-        // Lateral distance check
-        let lateral = ((player.pos - portal.local_pos) - normal * curr_dot).length();
-        if lateral > PORTAL_WIDTH * 0.6 {
+        };
+
+        // Player in the entry door's frame (quad on the XY plane, front +Z)
+        let source = door_surface(portal.room, entry);
+        let to_door = source.compute_affine().inverse();
+        let local = |pos: Vec2| {
+            to_door
+                .transform_point3(room_center(portal.room) + Vec3::new(pos.x, player.height, pos.y))
+        };
+        let prev = local(player.prev_pos);
+        let curr = local(player.pos);
+
+        // Check crossing (sign change) within the door's width
+        if prev.z * curr.z > 0.0 || prev.z == curr.z || curr.x.abs() > PORTAL_WIDTH * 0.6 {
             continue;
         }
 
-        // Exit at SAME door index in target room
-        let target_doors = get_doors(portal.target_room);
-        let exit_door = &target_doors[portal.door_index];
-        let exit_offset = if curr_dot < 0.0 { 1.2 } else { -1.2 };
-        let exit_pos = Vec2::new(exit_door.local_pos.x, exit_door.local_pos.z + exit_offset);
+        // Sealed: stay on this side, flare the seal and say what opens it
+        if locks.is_sealed(portal.room, portal.door_index) {
+            player.pos = player.prev_pos;
+            cam.translation =
+                room_center(player.room) + Vec3::new(player.pos.x, player.height, player.pos.y);
+            locks.deny(portal.room, portal.door_index);
+            if let Some(lock) = &entry.lock {
+                lock.show_hint(&mut card, &locale);
+            }
+            info!("🔒 Portal {} is sealed", portal.door_index + 1);
+            return;
+        }
 
+        // Same frame change as `sync_portal_cameras`: into the exit door,
+        // turned half a turn so the entry's front leads out of the exit's front
+        let (exit_pos, turn) = match get_doors(&tour, portal.target_room).get(portal.target_door) {
+            Some(exit) => {
+                let exit_tf = door_surface(portal.target_room, exit);
+                let through = exit_tf.rotation * Quat::from_rotation_y(PI);
+                let world = exit_tf.translation + through * curr;
+                let pos = world - room_center(portal.target_room);
+                (Vec2::new(pos.x, pos.z), exit.rotation + PI - entry.rotation)
+            }
+            // Room origin if the target room has no doors
+            None => (Vec2::ZERO, 0.0),
+        };
+
+        crossed.write(PortalCrossed {
+            from_room: player.room,
+            to_room: portal.target_room,
+        });
         player.room = portal.target_room;
         player.pos = exit_pos;
         player.prev_pos = exit_pos;
+
+        // Walking direction follows the camera yaw, so turning it keeps the stride
+        camera.yaw += turn;
+        cam.rotation = Quat::from_rotation_y(turn) * cam.rotation;
         cam.translation =
             room_center(portal.target_room) + Vec3::new(exit_pos.x, player.height, exit_pos.y);
 
+        let view_layers = room_view_layers(portal.target_room, Eye::Left);
         if let Some(mut layers) = layers_opt {
-            *layers = RenderLayers::layer(portal.target_room);
+            *layers = view_layers;
         } else {
-            cmd.entity(cam_entity)
-                .insert(RenderLayers::layer(portal.target_room));
+            cmd.entity(cam_entity).insert(view_layers);
         }
 
         info!(
-            "🌀 Portal {} → Room {}",
+            "🌀 Portal {} → Room {} (door {})",
             portal.door_index + 1,
-            portal.target_room + 1
+            portal.target_room + 1,
+            portal.target_door + 1
         );
         return;
     }
 }
+
+/// `InputEvent::NextRoom` (gamepad Y) travels on to the next room of the tour
+fn next_room_input(
+    mut input: MessageReader<InputEvent>,
+    mut travel: MessageWriter<TravelToRoom>,
+    player: Res<PlayerState>,
+    tour: Res<Tour>,
+) {
+    if input.read().any(|e| matches!(e, InputEvent::NextRoom)) && tour.room_count() > 1 {
+        travel.write(TravelToRoom {
+            room: (player.room + 1) % tour.room_count(),
+        });
+    }
+}
+
+/// Jumps cover the view with the crossing transition, then cut to the room's
+/// origin and reveal it the same way walking through a door does
+#[allow(clippy::too_many_arguments)]
+fn travel_to_room(
+    mut cmd: Commands,
+    mut events: MessageReader<TravelToRoom>,
+    mut crossed: MessageWriter<PortalCrossed>,
+    mut state: ResMut<PortalState>,
+    mut transition: ResMut<PortalTransitionState>,
+    mut player: ResMut<PlayerState>,
+    mut locks: ResMut<PortalLocks>,
+    mut card: ResMut<HotspotCard>,
+    locale: Res<Locale>,
+    mut cam_q: Query<(Entity, &mut Transform, Option<&mut RenderLayers>), With<PanoramaCamera>>,
+    tour: Res<Tour>,
+) {
+    if let Some(target) = events.read().last().map(|e| e.room) {
+        if target < tour.room_count() && target != player.room {
+            // Behind a sealed door: flare it and say what opens it instead
+            if let Some((room, door)) = locks.barrier(&tour, player.room, target) {
+                locks.deny(room, door);
+                if let Some(lock) = get_doors(&tour, room)
+                    .get(door)
+                    .and_then(|d| d.lock.as_ref())
+                {
+                    lock.show_hint(&mut card, &locale);
+                }
+                info!("🔒 {} is behind a sealed portal", tour.rooms[target].name);
+            } else {
+                state.travel = Some(target);
+                transition.covering = true;
+            }
+        }
+    }
+    let Some(target) = state.travel else {
+        return;
+    };
+    if transition.progress < 1.0 {
+        return;
+    }
+    let Ok((cam_entity, mut cam, layers_opt)) = cam_q.single_mut() else {
+        return;
+    };
+    state.travel = None;
+
+    crossed.write(PortalCrossed {
+        from_room: player.room,
+        to_room: target,
+    });
+    player.room = target;
+    player.pos = Vec2::ZERO;
+    player.prev_pos = Vec2::ZERO;
+    cam.translation = room_center(target) + Vec3::Y * player.height;
+
+    let view_layers = room_view_layers(target, Eye::Left);
+    if let Some(mut layers) = layers_opt {
+        *layers = view_layers;
+    } else {
+        cmd.entity(cam_entity).insert(view_layers);
+    }
+
+    info!(
+        "🧭 Travel → Room {}: {}",
+        target + 1,
+        tour.rooms[target].name
+    );
+}
@@ -0,0 +1,93 @@
+//! Portal projection - off-axis frustum through a door quad
+//!
+//! The portal camera keeps the exit door's orientation, so the door is its
+//! screen: the frustum edges pass through the quad's edges as seen from the
+//! eye, and the near plane lies in the door plane. Everything between the
+//! camera and the exit door is clipped away, whatever the view angle.
+
+use bevy::camera::{CameraProjection, SubCameraView};
+use bevy::math::Vec3A;
+use bevy::prelude::*;
+
+use super::{PORTAL_HEIGHT, PORTAL_WIDTH};
+
+/// Closest the eye may get to the door plane before the frustum degenerates
+const MIN_NEAR: f32 = 0.01;
+const FAR: f32 = 1000.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PortalProjection {
+    /// Distance from the eye to the door plane
+    pub near: f32,
+    /// Door edges on the near plane, relative to the eye
+    pub left: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub top: f32,
+}
+
+impl Default for PortalProjection {
+    fn default() -> Self {
+        Self::from_eye(Vec3::Z * 2.0)
+    }
+}
+
+impl PortalProjection {
+    /// Frustum for an eye in the door's local space (door quad centred on the
+    /// origin in the XY plane, facing +Z); `None` from behind the door
+    pub fn try_from_eye(eye: Vec3) -> Option<Self> {
+        (eye.z > 0.0).then(|| Self::from_eye(eye))
+    }
+
+    fn from_eye(eye: Vec3) -> Self {
+        let near = eye.z.max(MIN_NEAR);
+        let half = Vec2::new(PORTAL_WIDTH, PORTAL_HEIGHT) / 2.0;
+        Self {
+            near,
+            left: -half.x - eye.x,
+            right: half.x - eye.x,
+            bottom: -half.y - eye.y,
+            top: half.y - eye.y,
+        }
+    }
+}
+
+impl CameraProjection for PortalProjection {
+    /// Infinite reverse-Z, like Bevy's perspective projection, but off-axis
+    fn get_clip_from_view(&self) -> Mat4 {
+        let (l, r, b, t, n) = (self.left, self.right, self.bottom, self.top, self.near);
+        Mat4::from_cols(
+            Vec4::new(2.0 * n / (r - l), 0.0, 0.0, 0.0),
+            Vec4::new(0.0, 2.0 * n / (t - b), 0.0, 0.0),
+            Vec4::new((r + l) / (r - l), (t + b) / (t - b), 0.0, -1.0),
+            Vec4::new(0.0, 0.0, n, 0.0),
+        )
+    }
+
+    fn get_clip_from_view_for_sub(&self, _sub_view: &SubCameraView) -> Mat4 {
+        self.get_clip_from_view()
+    }
+
+    fn update(&mut self, _width: f32, _height: f32) {}
+
+    fn far(&self) -> f32 {
+        FAR
+    }
+
+    fn get_frustum_corners(&self, z_near: f32, z_far: f32) -> [Vec3A; 8] {
+        let corner = |x: f32, y: f32, z: f32| {
+            let scale = z.abs() / self.near;
+            Vec3A::new(x * scale, y * scale, z)
+        };
+        [
+            corner(self.right, self.bottom, z_near),
+            corner(self.right, self.top, z_near),
+            corner(self.left, self.top, z_near),
+            corner(self.left, self.bottom, z_near),
+            corner(self.right, self.bottom, z_far),
+            corner(self.right, self.top, z_far),
+            corner(self.left, self.top, z_far),
+            corner(self.left, self.bottom, z_far),
+        ]
+    }
+}
@@ -0,0 +1,301 @@
+//! Portal styles - door shape, frame kit and liquid-effect parameters
+//!
+//! Every shape is inscribed in the `PORTAL_WIDTH` × `PORTAL_HEIGHT` door
+//! rectangle and stands on its floor, so portal views, crossing and culling
+//! stay rectangle-based; the shape only decides which part of the view is
+//! shown and where the frame runs.
+
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::f32::consts::PI;
+
+use super::{FRAME_DEPTH, PORTAL_HEIGHT, PORTAL_WIDTH};
+
+/// Segments along a full circle of outline
+const ROUND_SEGMENTS: usize = 32;
+/// Moon gate threshold height, as a fraction of the radius below the centre
+const MOON_GATE_SILL: f32 = 0.8;
+
+/// Opening cut into the door
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PortalShape {
+    #[default]
+    Rectangle,
+    /// Straight sides under a round head
+    Arch,
+    /// Round opening the width of the door, resting on the floor
+    Circle,
+    /// Round opening standing on a flat threshold
+    MoonGate,
+}
+
+impl PortalShape {
+    pub fn shader_index(self) -> f32 {
+        match self {
+            Self::Rectangle => 0.0,
+            Self::Arch => 1.0,
+            Self::Circle => 2.0,
+            Self::MoonGate => 3.0,
+        }
+    }
+
+    /// Counter-clockwise outline around the door centre (x right, y up, metres)
+    pub fn outline(self) -> Vec<Vec2> {
+        let (hw, hh) = (PORTAL_WIDTH / 2.0, PORTAL_HEIGHT / 2.0);
+        let round = |center: Vec2, from: f32, to: f32, segments: usize| {
+            (0..=segments).map(move |i| {
+                center + Vec2::from_angle(from + (to - from) * i as f32 / segments as f32) * hw
+            })
+        };
+        match self {
+            Self::Rectangle => vec![
+                Vec2::new(-hw, -hh),
+                Vec2::new(hw, -hh),
+                Vec2::new(hw, hh),
+                Vec2::new(-hw, hh),
+            ],
+            Self::Arch => [Vec2::new(-hw, -hh), Vec2::new(hw, -hh)]
+                .into_iter()
+                .chain(round(Vec2::Y * (hh - hw), 0.0, PI, ROUND_SEGMENTS / 2))
+                .collect(),
+            Self::Circle => round(
+                Vec2::Y * (hw - hh),
+                -PI / 2.0,
+                3.0 * PI / 2.0,
+                ROUND_SEGMENTS,
+            )
+            .take(ROUND_SEGMENTS)
+            .collect(),
+            // Sill on the floor, so the gate can be walked through
+            Self::MoonGate => {
+                let sill = (-MOON_GATE_SILL).asin();
+                let center = Vec2::Y * (hw * MOON_GATE_SILL - hh);
+                round(center, sill, PI - sill, ROUND_SEGMENTS).collect()
+            }
+        }
+    }
+}
+
+/// Frame built around the opening
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FrameKit {
+    /// Bare opening
+    None,
+    /// Red lacquered wood with gold trim and a red glow
+    #[default]
+    Lacquer,
+    /// Weathered stone with bronze trim and a pale glow
+    Stone,
+    /// Green jade with gold trim and a cyan glow
+    Jade,
+}
+
+/// Materials of a frame kit: body, trim and inner glow
+pub struct FrameMaterials {
+    pub body: StandardMaterial,
+    pub trim: StandardMaterial,
+    pub glow: StandardMaterial,
+}
+
+impl FrameKit {
+    pub fn materials(self) -> Option<FrameMaterials> {
+        let trim = |color: Color, emissive: LinearRgba| StandardMaterial {
+            base_color: color,
+            metallic: 0.95,
+            perceptual_roughness: 0.15,
+            emissive,
+            ..default()
+        };
+        let glow = |color: Color, emissive: LinearRgba| StandardMaterial {
+            base_color: color,
+            emissive,
+            unlit: true,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        };
+        let gold = trim(
+            Color::srgb(1.0, 0.84, 0.0),
+            LinearRgba::new(0.4, 0.28, 0.0, 1.0),
+        );
+        match self {
+            Self::None => None,
+            Self::Lacquer => Some(FrameMaterials {
+                body: StandardMaterial {
+                    base_color: Color::srgb(0.25, 0.02, 0.02),
+                    perceptual_roughness: 0.4,
+                    metallic: 0.05,
+                    ..default()
+                },
+                trim: gold,
+                glow: glow(
+                    Color::srgba(0.86, 0.08, 0.24, 0.8),
+                    LinearRgba::new(3.0, 0.3, 0.5, 1.0),
+                ),
+            }),
+            Self::Stone => Some(FrameMaterials {
+                body: StandardMaterial {
+                    base_color: Color::srgb(0.42, 0.4, 0.37),
+                    perceptual_roughness: 0.9,
+                    ..default()
+                },
+                trim: trim(Color::srgb(0.55, 0.36, 0.2), LinearRgba::BLACK),
+                glow: glow(
+                    Color::srgba(0.9, 0.9, 1.0, 0.6),
+                    LinearRgba::new(1.2, 1.2, 1.5, 1.0),
+                ),
+            }),
+            Self::Jade => Some(FrameMaterials {
+                body: StandardMaterial {
+                    base_color: Color::srgb(0.1, 0.45, 0.3),
+                    perceptual_roughness: 0.25,
+                    ..default()
+                },
+                trim: gold,
+                glow: glow(
+                    Color::srgba(0.2, 0.9, 0.8, 0.8),
+                    LinearRgba::new(0.3, 2.5, 2.0, 1.0),
+                ),
+            }),
+        }
+    }
+}
+
+/// Liquid surface look, passed to `portal_effect.wgsl`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PortalEffect {
+    /// Multiplies the border, tendril and haze colours
+    pub tint: Color,
+    /// Scales the ink-flow wobble of the view (0 = still)
+    pub distortion: f32,
+    /// Scales the border glow, tendrils and sparkles (0 = none)
+    pub edge_glow: f32,
+}
+
+impl Default for PortalEffect {
+    fn default() -> Self {
+        Self {
+            tint: Color::WHITE,
+            distortion: 1.0,
+            edge_glow: 1.0,
+        }
+    }
+}
+
+/// Resolved look of a room's doors
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PortalStyle {
+    pub shape: PortalShape,
+    pub frame: FrameKit,
+    pub effect: PortalEffect,
+}
+
+/// Portal style entry in the tour manifest - unset fields fall back to the
+/// tour's style, then to the defaults
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub struct PortalStyleFile {
+    #[serde(default)]
+    pub shape: Option<PortalShape>,
+    #[serde(default)]
+    pub frame: Option<FrameKit>,
+    /// Linear RGB
+    #[serde(default)]
+    pub tint: Option<(f32, f32, f32)>,
+    #[serde(default)]
+    pub distortion: Option<f32>,
+    #[serde(default)]
+    pub edge_glow: Option<f32>,
+}
+
+impl PortalStyleFile {
+    pub fn over(&self, base: &PortalStyle) -> PortalStyle {
+        PortalStyle {
+            shape: self.shape.unwrap_or(base.shape),
+            frame: self.frame.unwrap_or(base.frame),
+            effect: PortalEffect {
+                tint: self
+                    .tint
+                    .map_or(base.effect.tint, |(r, g, b)| Color::linear_rgb(r, g, b)),
+                distortion: self.distortion.unwrap_or(base.effect.distortion).max(0.0),
+                edge_glow: self.edge_glow.unwrap_or(base.effect.edge_glow).max(0.0),
+            },
+        }
+    }
+}
+
+/// Flat mesh filling the outline, UVs spanning the whole door rectangle so the
+/// portal view lines up whatever the shape
+pub fn surface_mesh(shape: PortalShape) -> Mesh {
+    if shape == PortalShape::Rectangle {
+        return Rectangle::new(PORTAL_WIDTH, PORTAL_HEIGHT).into();
+    }
+    let outline = shape.outline();
+    let uv = |p: Vec2| [p.x / PORTAL_WIDTH + 0.5, 0.5 - p.y / PORTAL_HEIGHT];
+
+    // Every shape is convex: fan out from the middle of its outline
+    let center = outline.iter().sum::<Vec2>() / outline.len() as f32;
+    let mut pos = vec![[center.x, center.y, 0.0]];
+    let mut uvs = vec![uv(center)];
+    for p in &outline {
+        pos.push([p.x, p.y, 0.0]);
+        uvs.push(uv(*p));
+    }
+    let n = outline.len() as u32;
+    let idx = (0..n).flat_map(|i| [0, i + 1, (i + 1) % n + 1]).collect();
+
+    Mesh::new(PrimitiveTopology::TriangleList, default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, pos)
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![[0.0, 0.0, 1.0]; n as usize + 1],
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(idx))
+}
+
+/// Frame pieces for a shape, in the door's frame (origin on the floor)
+pub struct FrameMeshes {
+    pub body: Mesh,
+    pub trim: Mesh,
+    pub glow: Mesh,
+}
+
+/// Beams along the outline (none along the floor): the body just outside the
+/// opening, a thin trim strip around it and a glow line just inside
+pub fn frame_meshes(shape: PortalShape) -> FrameMeshes {
+    let outline = shape.outline();
+    let floor = -PORTAL_HEIGHT / 2.0 + 1e-3;
+    let edges: Vec<(Vec2, Vec2)> = outline
+        .iter()
+        .zip(outline.iter().cycle().skip(1))
+        .filter(|(a, b)| a.y > floor || b.y > floor)
+        .map(|(a, b)| (*a, *b))
+        .collect();
+
+    // `offset` moves the beam out from the opening, `z` towards the front
+    let beams = |width: f32, depth: f32, offset: f32, z: f32| {
+        let mut mesh: Option<Mesh> = None;
+        for (a, b) in &edges {
+            let along = *b - *a;
+            let outward = Vec2::new(along.y, -along.x).normalize_or_zero();
+            let mid = (*a + *b) / 2.0 + outward * offset;
+            // Overlap neighbouring beams so curved frames close up
+            let beam = Mesh::from(Cuboid::new(along.length() + width, width, depth))
+                .transformed_by(
+                    Transform::from_xyz(mid.x, mid.y + PORTAL_HEIGHT / 2.0, z)
+                        .with_rotation(Quat::from_rotation_z(along.to_angle())),
+                );
+            match &mut mesh {
+                Some(mesh) => mesh.merge(&beam).expect("cuboid meshes share attributes"),
+                None => mesh = Some(beam),
+            }
+        }
+        mesh.unwrap_or_else(|| Cuboid::default().into())
+    };
+
+    FrameMeshes {
+        body: beams(0.12, FRAME_DEPTH, 0.06, 0.0),
+        trim: beams(0.04, FRAME_DEPTH + 0.02, 0.07, 0.02),
+        glow: beams(0.02, 0.02, -0.02, 0.06),
+    }
+}
@@ -36,6 +36,8 @@ pub struct EnvironmentMood {
     pub warmth: f32,
     pub light_intensity: f32,
     pub is_night: bool,
+    /// Eases towards `is_night` so the clock's dusk doesn't snap the ambient colour
+    pub nightness: f32,
 }
 
 impl Default for EnvironmentMood {
@@ -44,6 +46,7 @@ impl Default for EnvironmentMood {
             warmth: 0.5,
             light_intensity: 1.0,
             is_night: false,
+            nightness: 0.0,
         }
     }
 }
@@ -55,7 +58,16 @@ fn setup_post_process(mut commands: Commands, cameras: Query<Entity, With<GameCa
     info!("✨ Dream post-processing initialized");
 }
 
-fn detect_environment_mood(ibl: Option<Res<IblLightProbe>>, mut mood: ResMut<EnvironmentMood>) {
+fn detect_environment_mood(
+    time: Res<Time>,
+    ibl: Option<Res<IblLightProbe>>,
+    mut mood: ResMut<EnvironmentMood>,
+) {
+    let target = if mood.is_night { 1.0 } else { 0.0 };
+    mood.nightness = mood
+        .nightness
+        .lerp(target, (time.delta_secs() * 0.5).min(1.0));
+
     let Some(ibl) = ibl else { return };
     if !ibl.analyzed || !ibl.is_changed() {
         return;
@@ -66,13 +78,16 @@ fn detect_environment_mood(ibl: Option<Res<IblLightProbe>>, mut mood: ResMut<Env
     let g = color.green;
     let b = color.blue;
 
+    let was_night = mood.is_night;
+    let was_warm = mood.warmth > 0.6;
     mood.warmth = ((r - b) * 0.5 + 0.5).clamp(0.0, 1.0);
     mood.light_intensity = (r + g + b) / 3.0;
     mood.is_night = mood.light_intensity < 0.3;
 
-    if mood.is_night {
+    // The world clock re-blends the probe continuously; only report changes
+    if mood.is_night && !was_night {
         info!("🌙 Night detected");
-    } else if mood.warmth > 0.6 {
+    } else if !mood.is_night && mood.warmth > 0.6 && (was_night || !was_warm) {
         info!(
             "☀️ Daylight detected (warmth={:.2}, intensity={:.2})",
             mood.warmth, mood.light_intensity
@@ -124,9 +139,9 @@ fn apply_effects(
     let warm_color = Color::srgb(1.0, 0.9, 0.8);
     let cool_color = Color::srgb(0.8, 0.85, 1.0);
 
-    ambient.color = if mood.is_night {
-        Color::srgb(0.6, 0.65, 0.9)
-    } else {
-        warm_color.mix(&cool_color, 1.0 - mood.warmth)
-    };
+    let night_color = Color::srgb(0.6, 0.65, 0.9);
+
+    ambient.color = warm_color
+        .mix(&cool_color, 1.0 - mood.warmth)
+        .mix(&night_color, mood.nightness);
 }
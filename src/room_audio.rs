@@ -1,12 +1,13 @@
 //! Room-based spatial audio with crossfade transitions
 //! - Per-room soundtrack with reverb/echo effect
-//! - Per-room narration (modelo(n).wav)
+//! - Per-room narration from the tour manifest
 //! - Smooth crossfade when transitioning through portals
 
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
 use std::time::Duration;
 
+use crate::tour::Tour;
 use crate::GameState;
 
 pub struct RoomAudioPlugin;
@@ -24,66 +25,75 @@ impl Plugin for RoomAudioPlugin {
 }
 
 const FADE_DURATION: Duration = Duration::from_millis(1500);
-const REVERB_PANNING: f32 = 0.15;
 
 #[derive(Resource, Default)]
 pub struct RoomAudioState {
     pub current_room: usize,
     prev_room: Option<usize>,
-    soundtracks: [Option<Handle<AudioInstance>>; 3],
-    narrations: [Option<Handle<AudioInstance>>; 3],
-    narration_played: [bool; 3],
+    soundtracks: Vec<Option<Handle<AudioInstance>>>,
+    narrations: Vec<Option<Handle<AudioInstance>>>,
+    narration_played: Vec<bool>,
     initialized: bool,
 }
 
-#[derive(Resource)]
-pub struct AudioAssets {
-    pub soundtracks: [Handle<AudioSource>; 3],
-    pub narrations: [Handle<AudioSource>; 3],
+impl RoomAudioState {
+    /// Whether a room's narration has been played through (or stopped)
+    pub fn narration_finished(&self, room: usize, instances: &Assets<AudioInstance>) -> bool {
+        if !self.narration_played.get(room).copied().unwrap_or(false) {
+            return false;
+        }
+        self.narrations[room]
+            .as_ref()
+            .and_then(|handle| instances.get(handle))
+            .is_none_or(|instance| matches!(instance.state(), PlaybackState::Stopped))
+    }
+
+    /// Soundtrack instance of a room, once it has started
+    pub fn soundtrack(&self, room: usize) -> Option<&Handle<AudioInstance>> {
+        self.soundtracks.get(room)?.as_ref()
+    }
+
+    /// Playback position (seconds) of a room's soundtrack while it is audible
+    pub fn soundtrack_position(
+        &self,
+        room: usize,
+        instances: &Assets<AudioInstance>,
+    ) -> Option<f64> {
+        match instances.get(self.soundtrack(room)?)?.state() {
+            PlaybackState::Playing { position } => Some(position),
+            _ => None,
+        }
+    }
 }
 
-fn setup_room_audio(
-    mut cmd: Commands,
-    asset_server: Res<AssetServer>,
-    audio: Res<Audio>,
-    mut state: ResMut<RoomAudioState>,
-) {
-    // Load audio assets - use .wav files that exist
-    let assets = AudioAssets {
-        soundtracks: [
-            asset_server.load("audio/modelo1.wav"),
-            asset_server.load("audio/modelo2.wav"),
-            asset_server.load("audio/modelo3.wav"),
-        ],
-        narrations: [
-            asset_server.load("audio/modelo1.wav"),
-            asset_server.load("audio/modelo2.wav"),
-            asset_server.load("audio/modelo3.wav"),
-        ],
-    };
+fn setup_room_audio(audio: Res<Audio>, tour: Res<Tour>, mut state: ResMut<RoomAudioState>) {
+    let rooms = tour.room_count();
+    state.soundtracks = vec![None; rooms];
+    state.narrations = vec![None; rooms];
+    state.narration_played = vec![false; rooms];
 
     // Start room 0 soundtrack
+    let first = &tour.rooms[0];
     let handle = audio
-        .play(assets.soundtracks[0].clone())
+        .play(first.soundtrack.clone())
         .looped()
         .with_volume(0.5)
+        .with_panning(first.panning)
         .fade_in(AudioTween::new(FADE_DURATION, AudioEasing::OutPowi(2)))
         .handle();
     state.soundtracks[0] = Some(handle);
     state.initialized = true;
 
-    cmd.insert_resource(assets);
-    info!("🎵 Room audio initialized");
+    info!("🎵 Room audio initialized ({} rooms)", rooms);
 }
 
 fn update_room_audio(
     audio: Res<Audio>,
-    assets: Option<Res<AudioAssets>>,
+    tour: Res<Tour>,
     mut state: ResMut<RoomAudioState>,
     mut instances: ResMut<Assets<AudioInstance>>,
     player_state: Option<Res<crate::player::PlayerState>>,
 ) {
-    let Some(assets) = assets else { return };
     let Some(player) = player_state else { return };
     if !state.initialized {
         return;
@@ -95,7 +105,7 @@ fn update_room_audio(
         let new_room = player.room;
 
         // Bounds check
-        if new_room >= 3 {
+        if new_room >= state.soundtracks.len() {
             return;
         }
 
@@ -111,19 +121,20 @@ fn update_room_audio(
         }
 
         // Start or fade in new room soundtrack
-        if state.soundtracks[new_room].is_none() {
-            let panning = match new_room {
-                0 => 0.0,
-                1 => -REVERB_PANNING,
-                2 => REVERB_PANNING,
-                _ => 0.0,
-            };
-
+        if let Some(handle) = &state.soundtracks[new_room] {
+            if let Some(instance) = instances.get_mut(handle) {
+                instance.set_playback_rate(
+                    1.0,
+                    AudioTween::new(FADE_DURATION, AudioEasing::OutPowi(2)),
+                );
+            }
+        } else {
+            let room = &tour.rooms[new_room];
             let handle = audio
-                .play(assets.soundtracks[new_room].clone())
+                .play(room.soundtrack.clone())
                 .looped()
                 .with_volume(0.5)
-                .with_panning(panning)
+                .with_panning(room.panning)
                 .fade_in(AudioTween::new(FADE_DURATION, AudioEasing::OutPowi(2)))
                 .handle();
 
@@ -141,18 +152,21 @@ fn update_room_audio(
 fn handle_narration(
     keys: Res<ButtonInput<KeyCode>>,
     audio: Res<Audio>,
-    assets: Option<Res<AudioAssets>>,
+    tour: Res<Tour>,
     mut state: ResMut<RoomAudioState>,
     mut instances: ResMut<Assets<AudioInstance>>,
 ) {
-    let Some(assets) = assets else { return };
     let room = state.current_room;
-    if room >= 3 {
+    if room >= state.narrations.len() {
         return;
     }
 
     // N key = play narration for current room
     if keys.just_pressed(KeyCode::KeyN) && !state.narration_played[room] {
+        let Some(narration) = tour.rooms[room].narration.clone() else {
+            return;
+        };
+
         // Stop any playing narration
         for handle in state.narrations.iter().flatten() {
             if let Some(instance) = instances.get_mut(handle) {
@@ -164,9 +178,9 @@ fn handle_narration(
         }
 
         let handle = audio
-            .play(assets.narrations[room].clone())
+            .play(narration)
             .with_volume(0.7)
-            .with_panning((room as f32 - 1.0) * 0.1)
+            .with_panning(tour.rooms[room].panning)
             .fade_in(AudioTween::new(
                 Duration::from_millis(300),
                 AudioEasing::Linear,
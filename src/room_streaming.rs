@@ -0,0 +1,202 @@
+//! Room streaming - per-room textures and models loaded around the player
+//!
+//! Only the starting room is loaded before `Viewing`. Rooms within
+//! `prefetch_hops` portal hops of the player load in the background; rooms
+//! more than `keep_hops` away drop their handles so Bevy frees them. Systems
+//! that build room content watch `RoomStreaming::assets` and spawn or despawn
+//! as rooms become ready or unload.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetPath, RecursiveDependencyLoadState, UntypedAssetId};
+use bevy::gltf::Gltf;
+use bevy::prelude::*;
+use bevy::tasks::futures::check_ready;
+use bevy::tasks::futures_lite::AsyncReadExt;
+use bevy::tasks::{IoTaskPool, Task};
+
+use crate::js_bridge::update_loading_progress;
+use crate::player::PlayerState;
+use crate::tour::Tour;
+use crate::world::GPano;
+use crate::GameState;
+
+/// Photo Sphere XMP sits in the JPEG's APP1 segment, well inside this
+const GPANO_HEAD_BYTES: u64 = 128 * 1024;
+
+pub struct RoomStreamingPlugin;
+
+impl Plugin for RoomStreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::StartingRoom), setup_room_streaming)
+            .add_systems(
+                Update,
+                (request_rooms, poll_rooms)
+                    .chain()
+                    .run_if(resource_exists::<RoomStreaming>)
+                    .run_if(in_state(GameState::StartingRoom).or(in_state(GameState::Viewing))),
+            )
+            .add_systems(
+                Update,
+                enter_starting_room.run_if(in_state(GameState::StartingRoom)),
+            );
+    }
+}
+
+/// Handles of a loaded (or loading) room
+pub struct RoomAssets {
+    pub panorama: Handle<Image>,
+    pub depth: Option<Handle<Image>>,
+    /// Time-of-day panoramas, in the room's `times` order
+    pub variants: Vec<Handle<Image>>,
+    pub character: Option<Handle<Gltf>>,
+    pub gpano: Option<GPano>,
+    gpano_task: Option<Task<Option<GPano>>>,
+    ready: bool,
+}
+
+#[derive(Resource)]
+pub struct RoomStreaming {
+    /// Rooms this many portal hops from the player are loaded ahead of time
+    pub prefetch_hops: usize,
+    /// Rooms further than this are unloaded
+    pub keep_hops: usize,
+    rooms: Vec<Option<RoomAssets>>,
+}
+
+impl RoomStreaming {
+    /// Assets of a room once everything it needs has loaded
+    pub fn assets(&self, room: usize) -> Option<&RoomAssets> {
+        self.rooms.get(room)?.as_ref().filter(|assets| assets.ready)
+    }
+
+    pub fn is_ready(&self, room: usize) -> bool {
+        self.assets(room).is_some()
+    }
+}
+
+fn setup_room_streaming(mut commands: Commands, tour: Res<Tour>) {
+    update_loading_progress("Loading first room...");
+    commands.insert_resource(RoomStreaming {
+        prefetch_hops: 1,
+        keep_hops: 2,
+        rooms: (0..tour.room_count()).map(|_| None).collect(),
+    });
+}
+
+fn enter_starting_room(
+    streaming: Res<RoomStreaming>,
+    player: Res<PlayerState>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    if streaming.is_ready(player.room) {
+        next.set(GameState::Viewing);
+    }
+}
+
+/// Start loading nearby rooms, release far ones
+fn request_rooms(
+    mut streaming: ResMut<RoomStreaming>,
+    tour: Res<Tour>,
+    player: Res<PlayerState>,
+    asset_server: Res<AssetServer>,
+) {
+    if !player.is_changed() && !streaming.is_added() {
+        return;
+    }
+
+    let hops = tour.room_hops(player.room);
+    let prefetch = streaming.prefetch_hops;
+    let keep = streaming.keep_hops.max(prefetch);
+
+    for (room, distance) in hops.into_iter().enumerate() {
+        let slot = &mut streaming.rooms[room];
+        let distance = distance.unwrap_or(usize::MAX);
+
+        if distance <= prefetch && slot.is_none() {
+            let def = &tour.rooms[room];
+            *slot = Some(RoomAssets {
+                panorama: asset_server.load(&def.panorama),
+                depth: def.depth.as_ref().map(|d| asset_server.load(&d.map)),
+                variants: def
+                    .times
+                    .iter()
+                    .map(|v| asset_server.load(&v.panorama))
+                    .collect(),
+                character: def.character.as_ref().map(|path| asset_server.load(path)),
+                gpano: None,
+                gpano_task: read_gpano(&asset_server, &def.panorama),
+                ready: false,
+            });
+            info!("📦 Loading room {}: {}", room, def.name);
+        } else if distance > keep && slot.take().is_some() {
+            info!("🗑️ Unloaded room {}: {}", room, tour.rooms[room].name);
+        }
+    }
+}
+
+/// Mark rooms ready once their handles (and metadata) have loaded or failed
+fn poll_rooms(
+    mut streaming: ResMut<RoomStreaming>,
+    tour: Res<Tour>,
+    asset_server: Res<AssetServer>,
+) {
+    for (room, slot) in streaming.rooms.iter_mut().enumerate() {
+        let Some(assets) = slot.as_mut().filter(|assets| !assets.ready) else {
+            continue;
+        };
+
+        if let Some(task) = &mut assets.gpano_task {
+            match check_ready(task) {
+                Some(gpano) => {
+                    assets.gpano = gpano;
+                    assets.gpano_task = None;
+                }
+                None => continue,
+            }
+        }
+
+        // A failed asset shouldn't hold the room back forever
+        let settled = |id: UntypedAssetId| {
+            matches!(
+                asset_server.get_recursive_dependency_load_state(id),
+                Some(
+                    RecursiveDependencyLoadState::Loaded | RecursiveDependencyLoadState::Failed(_)
+                )
+            )
+        };
+        let ready = settled(assets.panorama.id().untyped())
+            && assets.depth.iter().all(|h| settled(h.id().untyped()))
+            && assets.variants.iter().all(|h| settled(h.id().untyped()))
+            && assets.character.iter().all(|h| settled(h.id().untyped()));
+        if ready {
+            assets.ready = true;
+            info!("✅ Room {} ready: {}", room, tour.rooms[room].name);
+        }
+    }
+}
+
+/// Read the start of a JPEG panorama for its GPano XMP without decoding it
+fn read_gpano(asset_server: &AssetServer, path: &str) -> Option<Task<Option<GPano>>> {
+    let lower = path.to_ascii_lowercase();
+    if !(lower.ends_with(".jpg") || lower.ends_with(".jpeg")) {
+        return None;
+    }
+
+    let server = asset_server.clone();
+    let path = AssetPath::parse(path).into_owned();
+    Some(IoTaskPool::get().spawn(async move {
+        let source = server.get_source(path.source()).ok()?;
+        let reader: Box<dyn Reader> = source.reader().read(path.path()).await.ok()?;
+        let mut head = Vec::new();
+        reader
+            .take(GPANO_HEAD_BYTES)
+            .read_to_end(&mut head)
+            .await
+            .ok()?;
+        let gpano = GPano::parse(&head);
+        if let Some(gpano) = &gpano {
+            info!("🧭 {}: GPano pose {:?}", path, gpano);
+        }
+        gpano
+    }))
+}
@@ -0,0 +1,449 @@
+//! Room video skies - 360 equirect video on the current room's sky sphere
+//! - Desktop: numbered image sequence, streamed a few frames ahead
+//! - Web: HTMLVideoElement (WebM/MP4) copied into the sky texture each frame
+//! - Clock follows the room soundtrack so picture and sound stay in sync
+//! - V = play/pause, Shift+V = toggle loop
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::player::PlayerState;
+use crate::room_audio::RoomAudioState;
+use crate::room_streaming::RoomStreaming;
+use crate::tour::Tour;
+use crate::world::Skybox;
+use crate::GameState;
+
+pub struct RoomVideoPlugin;
+
+impl Plugin for RoomVideoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoomVideoState>().add_systems(
+            Update,
+            (
+                switch_room_video,
+                video_controls,
+                advance_video_clock,
+                update_video_frame,
+            )
+                .chain()
+                .run_if(in_state(GameState::Viewing)),
+        );
+    }
+}
+
+/// Frames requested ahead of the one on screen
+const PREFETCH_FRAMES: u32 = 8;
+
+/// Video sky declared for a room in the tour manifest
+#[derive(Deserialize, Clone, Debug)]
+pub struct VideoConfig {
+    /// Image sequence path - a run of `#` is replaced by the zero-padded frame number
+    pub frames: String,
+    pub frame_count: u32,
+    #[serde(default = "default_first_frame")]
+    pub first_frame: u32,
+    #[serde(default = "default_fps")]
+    pub fps: f32,
+    #[serde(default = "default_true")]
+    pub looping: bool,
+    /// Drive the clock from the room soundtrack's playback position
+    #[serde(default = "default_true")]
+    pub sync_to_soundtrack: bool,
+    /// WebM/MP4 played through an HTMLVideoElement on web (falls back to `frames`)
+    #[serde(default)]
+    pub web: Option<String>,
+}
+
+fn default_first_frame() -> u32 {
+    1
+}
+
+fn default_fps() -> f32 {
+    24.0
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl VideoConfig {
+    pub fn duration(&self) -> f64 {
+        self.frame_count as f64 / self.fps.max(1.0) as f64
+    }
+
+    /// Asset path of a 0-based frame index
+    pub fn frame_path(&self, index: u32) -> String {
+        let Some(start) = self.frames.find('#') else {
+            return self.frames.clone();
+        };
+        let width = self.frames[start..]
+            .chars()
+            .take_while(|&c| c == '#')
+            .count();
+        format!(
+            "{}{:0width$}{}",
+            &self.frames[..start],
+            self.first_frame + index,
+            &self.frames[start + width..],
+        )
+    }
+}
+
+/// Playback state of the active room's video
+#[derive(Resource, Default)]
+pub struct RoomVideoState {
+    pub room: Option<usize>,
+    pub time: f64,
+    pub playing: bool,
+    pub looping: bool,
+    frames: HashMap<u32, Handle<Image>>,
+    shown: Option<Handle<Image>>,
+    web_texture: Option<Handle<Image>>,
+}
+
+fn switch_room_video(
+    player: Res<PlayerState>,
+    tour: Res<Tour>,
+    streaming: Res<RoomStreaming>,
+    mut state: ResMut<RoomVideoState>,
+    mut images: ResMut<Assets<Image>>,
+    skies: Query<(&Skybox, &MeshMaterial3d<StandardMaterial>)>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+) {
+    let wanted = tour.rooms[player.room].video.as_ref().map(|_| player.room);
+    if state.room == wanted {
+        return;
+    }
+
+    // Put the poster frame back on the room we're leaving
+    if let Some(old) = state.room.take() {
+        if let Some(assets) = streaming.assets(old) {
+            set_sky_texture(old, assets.panorama.clone(), &skies, &mut mats);
+        }
+        if let Some(texture) = state.web_texture.take() {
+            images.remove(&texture);
+        }
+        #[cfg(target_arch = "wasm32")]
+        web::close();
+        info!("🎞️ Video stopped in {}", tour.rooms[old].name);
+    }
+    state.frames.clear();
+    state.shown = None;
+
+    let Some(room) = wanted else { return };
+    let Some(video) = &tour.rooms[room].video else {
+        return;
+    };
+
+    state.room = Some(room);
+    state.time = 0.0;
+    state.playing = true;
+    state.looping = video.looping;
+
+    #[cfg(target_arch = "wasm32")]
+    if let Some(url) = &video.web {
+        if web::open(url, video.looping) {
+            let texture = images.add(Image::new_fill(
+                bevy::render::render_resource::Extent3d {
+                    width: web::WIDTH,
+                    height: web::HEIGHT,
+                    depth_or_array_layers: 1,
+                },
+                bevy::render::render_resource::TextureDimension::D2,
+                &[0, 0, 0, 255],
+                bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
+                bevy::asset::RenderAssetUsages::RENDER_WORLD
+                    | bevy::asset::RenderAssetUsages::MAIN_WORLD,
+            ));
+            set_sky_texture(room, texture.clone(), &skies, &mut mats);
+            state.web_texture = Some(texture);
+        }
+    }
+
+    info!(
+        "🎞️ Video sky in {}: {} frames @ {} fps",
+        tour.rooms[room].name, video.frame_count, video.fps
+    );
+}
+
+fn video_controls(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut state: ResMut<RoomVideoState>,
+    tour: Res<Tour>,
+    audio: Res<RoomAudioState>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+) {
+    let Some(room) = state.room else { return };
+    if !keys.just_pressed(KeyCode::KeyV) {
+        return;
+    }
+
+    let shift = keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
+    if shift {
+        state.looping = !state.looping;
+        info!(
+            "🔁 Video loop: {}",
+            if state.looping { "ON" } else { "OFF" }
+        );
+        return;
+    }
+
+    state.playing = !state.playing;
+    let synced = tour.rooms[room]
+        .video
+        .as_ref()
+        .is_some_and(|v| v.sync_to_soundtrack);
+
+    // Keep the soundtrack paused with the picture so the clock stays locked
+    if synced {
+        if let Some(instance) = audio
+            .soundtrack(room)
+            .and_then(|handle| instances.get_mut(handle))
+        {
+            if state.playing {
+                instance.resume(AudioTween::default());
+            } else {
+                instance.pause(AudioTween::default());
+            }
+        }
+    }
+    info!(
+        "🎞️ Video {}",
+        if state.playing { "playing" } else { "paused" }
+    );
+}
+
+fn advance_video_clock(
+    time: Res<Time>,
+    tour: Res<Tour>,
+    audio: Res<RoomAudioState>,
+    instances: Res<Assets<AudioInstance>>,
+    mut state: ResMut<RoomVideoState>,
+) {
+    let Some(room) = state.room else { return };
+    let Some(video) = &tour.rooms[room].video else {
+        return;
+    };
+    if !state.playing {
+        return;
+    }
+
+    let soundtrack = video
+        .sync_to_soundtrack
+        .then(|| audio.soundtrack_position(room, &instances))
+        .flatten();
+    state.time = soundtrack.unwrap_or(state.time + time.delta_secs_f64());
+
+    let duration = video.duration();
+    if state.looping && duration > 0.0 {
+        state.time = state.time.rem_euclid(duration);
+    } else if state.time >= duration {
+        state.time = duration;
+        state.playing = false;
+        info!("🎞️ Video finished");
+    }
+}
+
+fn update_video_frame(
+    mut state: ResMut<RoomVideoState>,
+    tour: Res<Tour>,
+    asset_server: Res<AssetServer>,
+    skies: Query<(&Skybox, &MeshMaterial3d<StandardMaterial>)>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+    #[cfg(target_arch = "wasm32")] mut images: ResMut<Assets<Image>>,
+) {
+    let Some(room) = state.room else { return };
+    let Some(video) = &tour.rooms[room].video else {
+        return;
+    };
+
+    // Web: stream the HTMLVideoElement into the sky texture whenever it advances
+    #[cfg(target_arch = "wasm32")]
+    if let Some(texture) = state.web_texture.clone() {
+        web::sync(state.time, state.playing, state.looping);
+        if let (Some(bytes), Some(image)) = (web::read_frame(), images.get_mut(&texture)) {
+            image.data = Some(bytes);
+        }
+        return;
+    }
+
+    // Desktop: image sequence
+    let last = video.frame_count.saturating_sub(1);
+    let current = ((state.time * video.fps as f64) as u32).min(last);
+
+    let window: Vec<u32> = (0..PREFETCH_FRAMES)
+        .map(|ahead| current + ahead)
+        .map(|i| {
+            if state.looping {
+                i % video.frame_count.max(1)
+            } else {
+                i.min(last)
+            }
+        })
+        .collect();
+    state.frames.retain(|index, _| window.contains(index));
+    for &index in &window {
+        state
+            .frames
+            .entry(index)
+            .or_insert_with(|| asset_server.load(video.frame_path(index)));
+    }
+
+    // Keep the previous frame on screen until the current one has decoded
+    let Some(frame) = state.frames.get(&current).cloned() else {
+        return;
+    };
+    if state.shown.as_ref() == Some(&frame) || !asset_server.is_loaded(&frame) {
+        return;
+    }
+    set_sky_texture(room, frame.clone(), &skies, &mut mats);
+    state.shown = Some(frame);
+}
+
+fn set_sky_texture(
+    room: usize,
+    texture: Handle<Image>,
+    skies: &Query<(&Skybox, &MeshMaterial3d<StandardMaterial>)>,
+    mats: &mut Assets<StandardMaterial>,
+) {
+    for (sky, mat) in skies.iter() {
+        if sky.room != room {
+            continue;
+        }
+        if let Some(mat) = mats.get_mut(&mat.0) {
+            mat.base_color_texture = Some(texture.clone());
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::cell::{Cell, RefCell};
+    use wasm_bindgen::JsCast;
+    use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlVideoElement};
+
+    /// Size of the sky texture web video frames are copied into
+    pub const WIDTH: u32 = 2048;
+    pub const HEIGHT: u32 = 1024;
+    /// Seconds the element may drift from the clock before seeking
+    const MAX_DRIFT: f64 = 0.3;
+
+    struct WebVideo {
+        video: HtmlVideoElement,
+        ctx: CanvasRenderingContext2d,
+        /// Element time of the frame last copied out
+        copied: Cell<f64>,
+    }
+
+    thread_local! {
+        static VIDEO: RefCell<Option<WebVideo>> = const { RefCell::new(None) };
+    }
+
+    pub fn open(url: &str, looping: bool) -> bool {
+        let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+            return false;
+        };
+        let Some(video) = document
+            .create_element("video")
+            .ok()
+            .and_then(|el| el.dyn_into::<HtmlVideoElement>().ok())
+        else {
+            return false;
+        };
+        let Some(canvas) = document
+            .create_element("canvas")
+            .ok()
+            .and_then(|el| el.dyn_into::<HtmlCanvasElement>().ok())
+        else {
+            return false;
+        };
+        canvas.set_width(WIDTH);
+        canvas.set_height(HEIGHT);
+        let Some(ctx) = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()
+            .and_then(|ctx| ctx.dyn_into::<CanvasRenderingContext2d>().ok())
+        else {
+            return false;
+        };
+
+        // Sound comes from the room soundtrack
+        video.set_cross_origin(Some("anonymous"));
+        video.set_muted(true);
+        video.set_loop(looping);
+        let _ = video.set_attribute("playsinline", "");
+        video.set_src(url);
+        let _ = video.play();
+
+        let web = WebVideo {
+            video,
+            ctx,
+            copied: Cell::new(f64::NAN),
+        };
+        VIDEO.with(|v| *v.borrow_mut() = Some(web));
+        true
+    }
+
+    pub fn close() {
+        VIDEO.with(|v| {
+            if let Some(web) = v.borrow_mut().take() {
+                let _ = web.video.pause();
+                web.video.remove_attribute("src").ok();
+                web.video.load();
+            }
+        });
+    }
+
+    pub fn sync(time: f64, playing: bool, looping: bool) {
+        VIDEO.with(|v| {
+            let Some(web) = v.borrow().as_ref().map(|w| w.video.clone()) else {
+                return;
+            };
+            web.set_loop(looping);
+            if playing && web.paused() {
+                let _ = web.play();
+            } else if !playing && !web.paused() {
+                let _ = web.pause();
+            }
+            if (web.current_time() - time).abs() > MAX_DRIFT {
+                web.set_current_time(time);
+            }
+        });
+    }
+
+    /// Current frame as RGBA8 bytes, None until the element has data and
+    /// while it still shows the frame copied last time
+    pub fn read_frame() -> Option<Vec<u8>> {
+        VIDEO.with(|v| {
+            let v = v.borrow();
+            let web = v.as_ref()?;
+            if web.video.ready_state() < 2 {
+                return None;
+            }
+            // Paused or stalled: skip the canvas readback
+            let time = web.video.current_time();
+            if time == web.copied.get() {
+                return None;
+            }
+            web.ctx
+                .draw_image_with_html_video_element_and_dw_and_dh(
+                    &web.video,
+                    0.0,
+                    0.0,
+                    WIDTH as f64,
+                    HEIGHT as f64,
+                )
+                .ok()?;
+            let data = web
+                .ctx
+                .get_image_data(0.0, 0.0, WIDTH as f64, HEIGHT as f64)
+                .ok()?;
+            web.copied.set(time);
+            Some(data.data().0)
+        })
+    }
+}
@@ -0,0 +1,385 @@
+//! Tour manifest - data-driven room list (panoramas, characters, audio, doors, hotspots)
+//!
+//! Loaded from `assets/tours/*.tour.ron`. Room audio is registered as a
+//! dependency; panoramas, depth maps and models stay as paths and are streamed
+//! per room by `room_streaming`.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, UntypedAssetId, VisitAssetDependencies};
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+use bevy_kira_audio::AudioSource;
+use serde::Deserialize;
+
+use crate::hotspots::{HotspotAction, HotspotConfig};
+use crate::loading::TourAssets;
+use crate::locale::Locale;
+use crate::player::{WalkableArea, WalkableFile};
+use crate::portal_transition::TransitionConfig;
+use crate::portals::{DoorConfig, DoorLock, PortalStyle, PortalStyleFile, RoomPreview};
+use crate::room_video::VideoConfig;
+use crate::world::{
+    ClockFile, CoverageFile, DepthFile, RoomDepth, SkyCoverage, SkyProjection, StereoLayout,
+    TileConfig, TimeVariant, MAX_ROOMS,
+};
+
+pub struct TourPlugin;
+
+impl Plugin for TourPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<TourManifest>()
+            .register_asset_loader(TourManifestLoader);
+    }
+}
+
+/// A room with its audio resolved to handles (streamed assets stay as paths)
+#[derive(Clone)]
+pub struct TourRoom {
+    pub name: String,
+    pub panorama: String,
+    pub sky: SkyProjection,
+    pub coverage: Option<SkyCoverage>, // partial / cylindrical panoramas
+    pub stereo: StereoLayout,
+    pub tiles: Option<TileConfig>,
+    pub depth: Option<RoomDepth>,
+    pub exposure: f32, // EV stops applied to the sky (and HDR-derived lighting)
+    pub character: Option<String>,
+    pub soundtrack: Handle<AudioSource>,
+    pub narration: Option<Handle<AudioSource>>,
+    pub panning: f32,
+    pub video: Option<VideoConfig>,
+    pub doors: Vec<DoorConfig>,
+    pub hotspots: Vec<HotspotConfig>,
+    pub walkable: WalkableArea,
+    pub times: Vec<TimeVariant>, // time-of-day panoramas, sorted by hour
+    pub portal_style: PortalStyle, // look of this room's doors
+    pub preview: RoomPreview,    // label on the doors leading here
+}
+
+#[derive(TypePath, Clone)]
+pub struct TourManifest {
+    pub rooms: Vec<TourRoom>,
+    pub clock: ClockFile,
+    pub transition: TransitionConfig,
+    pub locale: Option<String>,
+}
+
+impl Asset for TourManifest {}
+
+// Manual impl: handles live inside `Vec<TourRoom>`, which the derive can't walk
+impl VisitAssetDependencies for TourManifest {
+    fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId)) {
+        for room in &self.rooms {
+            room.soundtrack.visit_dependencies(visit);
+            room.narration.visit_dependencies(visit);
+        }
+    }
+}
+
+/// Active tour - copied out of the manifest asset once loading completes
+#[derive(Resource, Clone)]
+pub struct Tour {
+    pub rooms: Vec<TourRoom>,
+    pub clock: ClockFile,
+    pub transition: TransitionConfig,
+    pub locale: Option<String>, // overrides the browser / system language
+}
+
+impl FromWorld for Tour {
+    fn from_world(world: &mut World) -> Self {
+        let handle = world.resource::<TourAssets>().manifest.clone();
+        let manifest = world
+            .resource::<Assets<TourManifest>>()
+            .get(&handle)
+            .expect("tour manifest loaded");
+        info!("🗺️ Tour: {} rooms", manifest.rooms.len());
+        let tour = Self {
+            rooms: manifest.rooms.clone(),
+            clock: manifest.clock,
+            transition: manifest.transition,
+            locale: manifest.locale.clone(),
+        };
+
+        // Visitor language for every translated string of the tour
+        let locale = Locale::detect(tour.locale.as_deref());
+        info!("🌐 Locale: {}", locale.0);
+        world.insert_resource(locale);
+        tour
+    }
+}
+
+impl Tour {
+    pub fn room_count(&self) -> usize {
+        self.rooms.len()
+    }
+
+    /// Portal hops from `from` to every room (`None` if unreachable)
+    pub fn room_hops(&self, from: usize) -> Vec<Option<usize>> {
+        let mut hops = vec![None; self.room_count()];
+        let mut queue = std::collections::VecDeque::from([from]);
+        hops[from] = Some(0);
+        while let Some(room) = queue.pop_front() {
+            let next = hops[room].unwrap_or(0) + 1;
+            for door in &self.rooms[room].doors {
+                if hops[door.target_room].is_none() {
+                    hops[door.target_room] = Some(next);
+                    queue.push_back(door.target_room);
+                }
+            }
+        }
+        hops
+    }
+
+    /// Render layers covering every room (for shared lights)
+    pub fn all_layers(&self) -> RenderLayers {
+        RenderLayers::from_layers(&(0..self.room_count()).collect::<Vec<_>>())
+    }
+}
+
+// === Manifest file format ===
+
+#[derive(Deserialize)]
+struct TourFile {
+    rooms: Vec<RoomFile>,
+    /// World clock for time-of-day panoramas
+    #[serde(default)]
+    clock: Option<ClockFile>,
+    /// Overlay played when crossing a portal
+    #[serde(default)]
+    transition: TransitionConfig,
+    /// Door shape, frame and effect for every room
+    #[serde(default)]
+    portal: PortalStyleFile,
+    /// Language for translated text (BCP 47 tag) - defaults to the visitor's
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RoomFile {
+    name: String,
+    panorama: String,
+    #[serde(default)]
+    sky: SkyProjection,
+    /// Overrides any GPano crop
+    #[serde(default)]
+    coverage: Option<CoverageFile>,
+    #[serde(default)]
+    stereo: StereoLayout,
+    #[serde(default)]
+    tiles: Option<TileConfig>,
+    #[serde(default)]
+    depth: Option<DepthFile>,
+    #[serde(default)]
+    exposure: f32,
+    #[serde(default)]
+    character: Option<String>,
+    soundtrack: String,
+    #[serde(default)]
+    narration: Option<String>,
+    #[serde(default)]
+    panning: f32,
+    #[serde(default)]
+    video: Option<VideoConfig>,
+    #[serde(default)]
+    doors: Vec<DoorFile>,
+    #[serde(default)]
+    hotspots: Vec<HotspotConfig>,
+    /// Floor polygon and obstacles - defaults to a 16×17 m box
+    #[serde(default)]
+    walkable: Option<WalkableFile>,
+    /// Panoramas for other times of day, blended by the world clock
+    #[serde(default)]
+    times: Vec<TimeVariant>,
+    /// Overrides the tour's portal style for this room's doors
+    #[serde(default)]
+    portal: PortalStyleFile,
+    /// Title, subtitle and thumbnail on the doors leading here
+    #[serde(default)]
+    preview: RoomPreview,
+}
+
+#[derive(Deserialize)]
+struct DoorFile {
+    id: String,
+    position: (f32, f32, f32),
+    rotation: f32,
+    target: usize,
+    /// Exit door id in the target room - defaults to the door leading back here
+    #[serde(default)]
+    target_door: Option<String>,
+    /// Keeps the door sealed until its condition holds
+    #[serde(default)]
+    lock: Option<DoorLock>,
+}
+
+/// Resolve a door link to an index into the target room's doors
+fn resolve_exit(rooms: &[RoomFile], from_room: usize, door: &DoorFile) -> usize {
+    let exits = &rooms[door.target].doors;
+    let by_id = door
+        .target_door
+        .as_ref()
+        .and_then(|id| exits.iter().position(|d| &d.id == id));
+
+    if by_id.is_none() {
+        if let Some(id) = &door.target_door {
+            warn!(
+                "⚠️ {}: exit door '{}' not found in {}",
+                rooms[from_room].name, id, rooms[door.target].name
+            );
+        }
+    }
+
+    by_id
+        .or_else(|| exits.iter().position(|d| d.target == from_room))
+        .unwrap_or(0)
+}
+
+#[derive(Default)]
+struct TourManifestLoader;
+
+impl AssetLoader for TourManifestLoader {
+    type Asset = TourManifest;
+    type Settings = ();
+    type Error = BevyError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<TourManifest, BevyError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: TourFile = ron::de::from_bytes(&bytes)?;
+        let room_count = file.rooms.len();
+        if room_count == 0 {
+            return Err("tour manifest has no rooms".into());
+        }
+        if room_count > MAX_ROOMS {
+            return Err(format!(
+                "tour manifest has {room_count} rooms, at most {MAX_ROOMS} are supported"
+            )
+            .into());
+        }
+
+        // Drop doors pointing outside the tour before resolving exit links
+        let mut rooms = file.rooms;
+        for room in &mut rooms {
+            let name = &room.name;
+            room.doors.retain(|door| {
+                let valid = door.target < room_count;
+                if !valid {
+                    warn!(
+                        "⚠️ {}: door '{}' targets missing room {}",
+                        name, door.id, door.target
+                    );
+                }
+                valid
+            });
+            room.hotspots.retain(|hotspot| match hotspot.action {
+                HotspotAction::Jump(target) if target >= room_count => {
+                    warn!("⚠️ {}: hotspot jumps to missing room {}", name, target);
+                    false
+                }
+                _ => true,
+            });
+            for variant in &mut room.times {
+                variant.hour = variant.hour.rem_euclid(24.0);
+            }
+            room.times.sort_by(|a, b| a.hour.total_cmp(&b.hour));
+        }
+
+        // Locks that could never open would seal their doors for good
+        let narrations: Vec<bool> = rooms.iter().map(|r| r.narration.is_some()).collect();
+        let hotspots: Vec<usize> = rooms.iter().map(|r| r.hotspots.len()).collect();
+        for room in &mut rooms {
+            let name = &room.name;
+            for door in &mut room.doors {
+                let Some(lock) = &door.lock else {
+                    continue;
+                };
+                if let Err(problem) = lock.when.check(&narrations, &hotspots) {
+                    warn!(
+                        "⚠️ {}: door '{}' lock {}, leaving it open",
+                        name, door.id, problem
+                    );
+                    door.lock = None;
+                }
+            }
+        }
+
+        let links: Vec<Vec<usize>> = rooms
+            .iter()
+            .enumerate()
+            .map(|(index, room)| {
+                room.doors
+                    .iter()
+                    .map(|door| resolve_exit(&rooms, index, door))
+                    .collect()
+            })
+            .collect();
+
+        let portal_style = file.portal.over(&PortalStyle::default());
+        let rooms = rooms
+            .into_iter()
+            .zip(links)
+            .map(|(room, links)| {
+                let doors = room
+                    .doors
+                    .into_iter()
+                    .zip(links)
+                    .enumerate()
+                    .map(|(door_index, (door, target_door))| DoorConfig {
+                        id: door.id,
+                        local_pos: Vec3::new(door.position.0, door.position.1, door.position.2),
+                        rotation: door.rotation,
+                        target_room: door.target,
+                        target_door,
+                        door_index,
+                        lock: door.lock,
+                    })
+                    .collect();
+
+                TourRoom {
+                    panorama: room.panorama,
+                    sky: room.sky,
+                    coverage: room.coverage.map(SkyCoverage::from),
+                    stereo: room.stereo,
+                    tiles: room.tiles,
+                    depth: room.depth.map(|depth| RoomDepth {
+                        map: depth.map,
+                        encoding: depth.encoding,
+                        near: depth.near,
+                        far: depth.far,
+                        capture_height: depth.capture_height,
+                    }),
+                    exposure: room.exposure,
+                    character: room.character,
+                    soundtrack: load_context.load(room.soundtrack),
+                    narration: room.narration.map(|path| load_context.load(path)),
+                    panning: room.panning,
+                    video: room.video,
+                    doors,
+                    hotspots: room.hotspots,
+                    walkable: room.walkable.map(WalkableArea::from).unwrap_or_default(),
+                    times: room.times,
+                    portal_style: room.portal.over(&portal_style),
+                    preview: room.preview,
+                    name: room.name,
+                }
+            })
+            .collect();
+
+        Ok(TourManifest {
+            rooms,
+            clock: file.clock.unwrap_or_default(),
+            transition: file.transition,
+            locale: file.locale,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tour.ron"]
+    }
+}
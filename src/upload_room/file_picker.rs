@@ -5,11 +5,15 @@ use bevy::gltf::Gltf;
 use bevy::prelude::*;
 use std::sync::Mutex;
 
-use super::{UploadModel, UploadSphere, UploadState};
+use super::{UploadCap, UploadModel, UploadSphere, UploadState};
+use crate::world::{create_blurred_cap, create_sky_sphere, CapFill, GPano, SkyCoverage};
 
 pub static PENDING_IMAGE: Mutex<Option<Vec<u8>>> = Mutex::new(None);
 pub static PENDING_GLB: Mutex<Option<Vec<u8>>> = Mutex::new(None);
 
+/// Matches the sky sphere spawned in `setup_upload_room`
+const UPLOAD_SKY_RADIUS: f32 = 50.0;
+
 #[derive(Clone, Copy)]
 pub enum FileKind {
     Image,
@@ -25,7 +29,7 @@ pub fn pick_file(kind: FileKind) {
     {
         std::thread::spawn(move || {
             let filter = match kind {
-                FileKind::Image => ("Images", &["jpg", "jpeg", "png"][..]),
+                FileKind::Image => ("Images", &["jpg", "jpeg", "png", "hdr", "exr"][..]),
                 FileKind::Model => ("GLB", &["glb"][..]),
             };
             if let Some(path) = rfd::FileDialog::new()
@@ -74,7 +78,7 @@ pub fn pick_file(kind: FileKind) {
 
         input.set_type("file");
         input.set_accept(match kind {
-            FileKind::Image => "image/jpeg,image/png",
+            FileKind::Image => "image/jpeg,image/png,.hdr,.exr",
             FileKind::Model => ".glb",
         });
 
@@ -124,36 +128,79 @@ pub fn pick_file(kind: FileKind) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn poll_file_data(
+    mut commands: Commands,
     mut images: ResMut<Assets<Image>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    sphere_q: Query<&MeshMaterial3d<StandardMaterial>, With<UploadSphere>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut sphere_q: Query<
+        (
+            Entity,
+            &MeshMaterial3d<StandardMaterial>,
+            &mut Mesh3d,
+            &mut Transform,
+        ),
+        With<UploadSphere>,
+    >,
+    caps: Query<Entity, With<UploadCap>>,
     asset_server: Res<AssetServer>,
     mut state: ResMut<UploadState>,
 ) {
     if let Some(data) = PENDING_IMAGE.lock().unwrap().take() {
         if let Ok(img) = image::load_from_memory(&data) {
-            let rgba = img.to_rgba8();
-            let (w, h) = rgba.dimensions();
-            let tex = Image::new(
-                bevy::render::render_resource::Extent3d {
-                    width: w,
-                    height: h,
-                    depth_or_array_layers: 1,
-                },
-                bevy::render::render_resource::TextureDimension::D2,
-                rgba.into_raw(),
-                bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb,
-                RenderAssetUsages::RENDER_WORLD,
-            );
+            let tex = decode_panorama(img);
+
+            // Photo Sphere pose and crop; wide images without metadata are
+            // treated as cylindrical sweeps
+            let gpano = GPano::parse(&data);
+            let coverage = match gpano.and_then(|gpano| gpano.crop) {
+                Some(crop) => Some(SkyCoverage::cropped(crop)),
+                None => SkyCoverage::from_aspect(tex.width(), tex.height()),
+            };
+            let cap = coverage
+                .and_then(|_| create_blurred_cap(&tex, Rect::new(0.0, 0.0, 1.0, 1.0)))
+                .map(|cap| images.add(cap));
             let handle = images.add(tex);
 
-            if let Ok(mat_h) = sphere_q.single() {
+            if let Ok((sphere, mat_h, mut mesh, mut transform)) = sphere_q.single_mut() {
                 if let Some(mat) = materials.get_mut(&mat_h.0) {
                     mat.base_color_texture = Some(handle);
                     mat.base_color = Color::WHITE;
                     info!("✅ Panorama applied");
                 }
+
+                transform.rotation = gpano.map_or(Quat::IDENTITY, |gpano| gpano.rotation());
+                mesh.0 = meshes.add(match coverage {
+                    Some(coverage) => coverage.create_mesh(UPLOAD_SKY_RADIUS),
+                    None => create_sky_sphere(UPLOAD_SKY_RADIUS),
+                });
+                if let Some(gpano) = gpano {
+                    info!("🧭 GPano pose {:?}", gpano);
+                }
+
+                for cap in &caps {
+                    commands.entity(cap).despawn();
+                }
+                if let Some(coverage) = coverage {
+                    let color = match coverage.cap {
+                        CapFill::Color(r, g, b) => Color::linear_rgb(r, g, b),
+                        CapFill::Blur => Color::WHITE,
+                    };
+                    commands.entity(sphere).with_child((
+                        Mesh3d(meshes.add(create_sky_sphere(UPLOAD_SKY_RADIUS))),
+                        MeshMaterial3d(materials.add(StandardMaterial {
+                            base_color: color,
+                            base_color_texture: cap,
+                            uv_transform: coverage.cap_uv_transform(),
+                            unlit: true,
+                            cull_mode: None,
+                            ..default()
+                        })),
+                        Transform::default(),
+                        UploadCap,
+                    ));
+                }
             }
         }
     }
@@ -180,6 +227,43 @@ pub fn poll_file_data(
     }
 }
 
+/// 8-bit images stay sRGB; HDR/EXR keep their float radiance as `Rgba16Float`
+fn decode_panorama(img: image::DynamicImage) -> Image {
+    use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+    let size = Extent3d {
+        width: img.width(),
+        height: img.height(),
+        depth_or_array_layers: 1,
+    };
+
+    if matches!(
+        img,
+        image::DynamicImage::ImageRgb32F(_) | image::DynamicImage::ImageRgba32F(_)
+    ) {
+        let rgba = img.to_rgba32f();
+        let mut tex = Image::new_fill(
+            size,
+            TextureDimension::D2,
+            &[0; 8],
+            TextureFormat::Rgba16Float,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        for (x, y, p) in rgba.enumerate_pixels() {
+            let _ = tex.set_color_at(x, y, Color::linear_rgba(p[0], p[1], p[2], p[3]));
+        }
+        return tex;
+    }
+
+    Image::new(
+        size,
+        TextureDimension::D2,
+        img.to_rgba8().into_raw(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
 pub fn load_pending_model(
     mut commands: Commands,
     gltfs: Res<Assets<Gltf>>,
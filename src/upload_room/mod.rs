@@ -105,5 +105,9 @@ impl Default for UploadState {
 #[derive(Component)]
 pub struct UploadSphere;
 
+/// Fills the sky around a partial panorama on the upload sphere
+#[derive(Component)]
+pub struct UploadCap;
+
 #[derive(Component)]
 pub struct UploadModel;
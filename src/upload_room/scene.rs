@@ -1,11 +1,9 @@
 //! 3D scene setup for upload room
 
-use bevy::asset::RenderAssetUsages;
-use bevy::mesh::{Indices, PrimitiveTopology};
 use bevy::prelude::*;
-use std::f32::consts::PI;
 
 use super::UploadSphere;
+use crate::world::create_sky_sphere;
 
 #[derive(Component)]
 pub struct AmbientOrb;
@@ -16,7 +14,7 @@ pub fn setup_upload_room(
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
     // Sky sphere
-    let sphere = meshes.add(create_sphere(50.0, 64, 32));
+    let sphere = meshes.add(create_sky_sphere(50.0));
     let mat = materials.add(StandardMaterial {
         base_color: Color::srgb(0.05, 0.08, 0.15),
         unlit: true,
@@ -96,44 +94,3 @@ pub fn rotate_ambient_light(time: Res<Time>, mut orbs: Query<&mut Transform, Wit
         t.rotate_y(time.delta_secs() * 0.3);
     }
 }
-
-fn create_sphere(radius: f32, sectors: u32, stacks: u32) -> Mesh {
-    let mut pos = Vec::new();
-    let mut norm = Vec::new();
-    let mut uv = Vec::new();
-    let mut idx = Vec::new();
-
-    for i in 0..=stacks {
-        let v = i as f32 / stacks as f32;
-        let phi = PI * v;
-        for j in 0..=sectors {
-            let u = j as f32 / sectors as f32;
-            let theta = 2.0 * PI * u;
-            let (x, y, z) = (
-                radius * phi.sin() * theta.cos(),
-                radius * phi.cos(),
-                radius * phi.sin() * theta.sin(),
-            );
-            pos.push([x, y, z]);
-            norm.push([x / radius, y / radius, z / radius]);
-            uv.push([1.0 - u, v]);
-        }
-    }
-
-    for i in 0..stacks {
-        for j in 0..sectors {
-            let a = i * (sectors + 1) + j;
-            let b = a + sectors + 1;
-            idx.extend([a, a + 1, b, b, a + 1, b + 1]);
-        }
-    }
-
-    Mesh::new(
-        PrimitiveTopology::TriangleList,
-        RenderAssetUsages::RENDER_WORLD,
-    )
-    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, pos)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, norm)
-    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uv)
-    .with_inserted_indices(Indices::U32(idx))
-}
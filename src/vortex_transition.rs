@@ -1,4 +1,5 @@
-use crate::world::Skybox;
+use crate::tour::Tour;
+use crate::world::{SkyCubeMaterial, Skybox};
 use crate::GameState;
 use bevy::prelude::*;
 use bevy::render::render_resource::AsBindGroup;
@@ -19,10 +20,9 @@ impl Plugin for VortexTransitionPlugin {
     }
 }
 
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct TransitionState {
     current_index: usize,
-    paths: Vec<&'static str>,
     transitioning: bool,
     progress: f32,
     current_texture: Option<Handle<Image>>,
@@ -30,24 +30,6 @@ pub struct TransitionState {
     loading: bool,
 }
 
-impl Default for TransitionState {
-    fn default() -> Self {
-        Self {
-            current_index: 0,
-            paths: vec![
-                "panoramas/demo.jpg",
-                "panoramas/demo2.jpg",
-                "panoramas/demo3.jpg",
-            ],
-            transitioning: false,
-            progress: 0.0,
-            current_texture: None,
-            next_texture: None,
-            loading: false,
-        }
-    }
-}
-
 #[derive(Asset, TypePath, AsBindGroup, Clone)]
 pub struct VortexMaterial {
     #[texture(0)]
@@ -71,6 +53,7 @@ pub struct VortexSphere;
 fn handle_transition_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut state: ResMut<TransitionState>,
+    tour: Res<Tour>,
     asset_server: Res<AssetServer>,
     sphere: Query<&MeshMaterial3d<StandardMaterial>, With<Skybox>>,
     materials: Res<Assets<StandardMaterial>>,
@@ -87,14 +70,14 @@ fn handle_transition_input(
             }
         }
 
-        let next = (state.current_index + 1) % state.paths.len();
-        let path = state.paths[next];
+        let next = (state.current_index + 1) % tour.room_count();
+        let room = &tour.rooms[next];
 
-        state.next_texture = Some(asset_server.load(path));
+        state.next_texture = Some(asset_server.load(&room.panorama));
         state.loading = true;
         state.current_index = next;
 
-        info!("🌀 Loading panorama {}: {}", next, path);
+        info!("🌀 Loading panorama {}: {}", next, room.name);
     }
 }
 
@@ -104,14 +87,15 @@ fn animate_transition(
     mut state: ResMut<TransitionState>,
     asset_server: Res<AssetServer>,
     sphere_std: Query<
-        (Entity, &MeshMaterial3d<StandardMaterial>),
-        (With<Skybox>, Without<VortexSphere>),
+        (Entity, &Skybox, Option<&MeshMaterial3d<StandardMaterial>>),
+        Without<VortexSphere>,
     >,
     sphere_vortex: Query<(Entity, &MeshMaterial3d<VortexMaterial>), With<VortexSphere>>,
     mut commands: Commands,
     mut vortex_materials: ResMut<Assets<VortexMaterial>>,
     mut std_materials: ResMut<Assets<StandardMaterial>>,
     player: Option<Res<crate::player::PlayerState>>,
+    tour: Res<Tour>,
 ) {
     let current_room = player.map(|p| p.room).unwrap_or(0);
 
@@ -126,14 +110,20 @@ fn animate_transition(
                     state.progress = 0.0;
 
                     // Find current room's skybox and swap to vortex material
-                    if let Some((entity, std_mat_handle)) = sphere_std.iter().next() {
-                        let current = if let Some(mat) = std_materials.get(&std_mat_handle.0) {
-                            mat.base_color_texture
-                                .clone()
-                                .unwrap_or_else(|| handle.clone())
-                        } else {
-                            handle.clone()
-                        };
+                    if let Some((entity, skybox, std_mat_handle)) = sphere_std
+                        .iter()
+                        .find(|(_, skybox, _)| skybox.room == current_room)
+                    {
+                        // Cubemap skies carry no equirect texture - use the room panorama
+                        let current = std_mat_handle
+                            .and_then(|h| std_materials.get(&h.0))
+                            .and_then(|mat| mat.base_color_texture.clone())
+                            .or_else(|| {
+                                tour.rooms
+                                    .get(skybox.room)
+                                    .map(|r| asset_server.load(&r.panorama))
+                            })
+                            .unwrap_or_else(|| handle.clone());
 
                         state.current_texture = Some(current.clone());
 
@@ -146,6 +136,7 @@ fn animate_transition(
                         commands
                             .entity(entity)
                             .remove::<MeshMaterial3d<StandardMaterial>>()
+                            .remove::<MeshMaterial3d<SkyCubeMaterial>>()
                             .insert((MeshMaterial3d(vortex), VortexSphere));
                     }
 
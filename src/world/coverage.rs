@@ -0,0 +1,230 @@
+//! Sky coverage - partial-sphere and cylindrical panoramas with a cap behind them
+//!
+//! Phone panoramas often cover 360×90 or 180 degrees. The panorama mesh is built
+//! only over the covered area (slightly inside the sky radius) and a full
+//! sphere behind it fills the rest with a solid colour or the image's blurred
+//! edges, so nothing is stretched.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::math::Affine2;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use serde::Deserialize;
+use std::f32::consts::PI;
+
+use super::{create_partial_sky_sphere, is_hdr_format};
+
+/// Blurred cap resolution - low enough that clamped edges read as a soft gradient
+const CAP_WIDTH: u32 = 64;
+const CAP_HEIGHT: u32 = 32;
+/// Source samples averaged per cap texel (per axis)
+const CAP_SAMPLES: u32 = 6;
+/// Panorama mesh sits inside the cap sphere so it draws on top
+const COVERAGE_INSET: f32 = 0.98;
+/// Steepest cylinder edge - keeps the wall height finite
+const MAX_CYLINDER_ELEVATION: f32 = 80.0;
+
+/// Surface the panorama was projected onto when captured
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoverageShape {
+    /// Equirect rows are evenly spaced in elevation
+    #[default]
+    Sphere,
+    /// Rows are evenly spaced in height on a cylinder (phone sweep panoramas)
+    Cylinder,
+}
+
+/// What fills the part of the sky the panorama doesn't cover
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum CapFill {
+    /// The image's edges, blurred and stretched out to the poles/back
+    #[default]
+    Blur,
+    /// Solid colour (linear RGB)
+    Color(f32, f32, f32),
+}
+
+/// Coverage entry in the tour manifest (angles in degrees, centred on the image centre)
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct CoverageFile {
+    #[serde(default)]
+    pub shape: CoverageShape,
+    #[serde(default = "default_hfov")]
+    pub hfov: f32,
+    #[serde(default = "default_vfov")]
+    pub vfov: f32,
+    #[serde(default)]
+    pub cap: CapFill,
+}
+
+fn default_hfov() -> f32 {
+    360.0
+}
+
+fn default_vfov() -> f32 {
+    180.0
+}
+
+/// Resolved coverage of a room's panorama
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkyCoverage {
+    pub shape: CoverageShape,
+    /// Covered part of the full equirect (normalized UV rect)
+    pub area: Rect,
+    pub cap: CapFill,
+}
+
+impl From<CoverageFile> for SkyCoverage {
+    fn from(file: CoverageFile) -> Self {
+        let half = Vec2::new(
+            file.hfov.clamp(1.0, 360.0) / 720.0,
+            file.vfov.clamp(1.0, 180.0) / 360.0,
+        );
+        Self {
+            shape: file.shape,
+            area: Rect::from_center_half_size(Vec2::splat(0.5), half),
+            cap: file.cap,
+        }
+    }
+}
+
+impl SkyCoverage {
+    /// Cropped spherical panorama (e.g. from GPano metadata)
+    pub fn cropped(area: Rect) -> Self {
+        Self {
+            shape: CoverageShape::Sphere,
+            area,
+            cap: CapFill::Blur,
+        }
+    }
+
+    /// Guess coverage from an image without metadata: wider than 2:1 is a
+    /// full-circle cylindrical sweep, anything else is a full sphere
+    pub fn from_aspect(width: u32, height: u32) -> Option<Self> {
+        let aspect = width as f32 / height.max(1) as f32;
+        (aspect > 2.1).then(|| {
+            CoverageFile {
+                shape: CoverageShape::Cylinder,
+                hfov: 360.0,
+                vfov: 360.0 / aspect,
+                cap: CapFill::Blur,
+            }
+            .into()
+        })
+    }
+
+    /// Panorama mesh for the covered area, with UVs spanning 0..1 across the image
+    pub fn create_mesh(&self, r: f32) -> Mesh {
+        match self.shape {
+            CoverageShape::Sphere => create_partial_sky_sphere(r * COVERAGE_INSET, self.area),
+            CoverageShape::Cylinder => create_sky_cylinder(r * COVERAGE_INSET, self.area),
+        }
+    }
+
+    /// Maps the cap sphere's full-panorama UVs onto the blurred cap image
+    pub fn cap_uv_transform(&self) -> Affine2 {
+        let scale = self.area.size().recip();
+        Affine2::from_scale_angle_translation(scale, 0.0, -self.area.min * scale)
+    }
+}
+
+/// Marks a cap sphere; `tint` is scaled by the room exposure
+#[derive(Component)]
+pub struct SkyCapFill {
+    pub room: usize,
+    pub tint: LinearRgba,
+}
+
+/// Cylinder wall whose height is linear in image rows; `area.y` gives the
+/// elevation range, scaled so the top and bottom edges stay within `r`
+pub fn create_sky_cylinder(r: f32, area: Rect) -> Mesh {
+    let limit = MAX_CYLINDER_ELEVATION.to_radians();
+    let top = ((0.5 - area.min.y) * PI).clamp(-limit, limit);
+    let bottom = ((0.5 - area.max.y) * PI).clamp(-limit, limit);
+    let wall = r * top.abs().max(bottom.abs()).cos();
+    let (y_top, y_bottom) = (wall * top.tan(), wall * bottom.tan());
+
+    let sec = ((96.0 * area.width()).ceil() as u32).max(4);
+    let stk = 8u32;
+    let mut pos = Vec::new();
+    let mut nrm = Vec::new();
+    let mut uv = Vec::new();
+    let mut idx = Vec::new();
+
+    for i in 0..=stk {
+        let t = i as f32 / stk as f32;
+        let y = y_top + t * (y_bottom - y_top);
+        for j in 0..=sec {
+            let s = j as f32 / sec as f32;
+            let th = 2.0 * PI * (1.0 - area.max.x + s * area.width());
+            let (x, z) = (wall * th.cos(), wall * th.sin());
+            pos.push([x, y, z]);
+            nrm.push([-th.cos(), 0.0, -th.sin()]);
+            uv.push([1.0 - s, t]);
+        }
+    }
+    for i in 0..stk {
+        for j in 0..sec {
+            let a = i * (sec + 1) + j;
+            let b = a + sec + 1;
+            idx.extend([a, a + 1, b, b, a + 1, b + 1]);
+        }
+    }
+    Mesh::new(PrimitiveTopology::TriangleList, default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, pos)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, nrm)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uv)
+        .with_inserted_indices(Indices::U32(idx))
+}
+
+/// Small box-filtered copy of `region` of the panorama, linearly sampled and
+/// clamped so its edges smear smoothly over the uncovered sky
+pub fn create_blurred_cap(src: &Image, region: Rect) -> Option<Image> {
+    let (w, h) = (src.width(), src.height());
+    if w == 0 || h == 0 {
+        return None;
+    }
+    let hdr = is_hdr_format(src.texture_descriptor.format);
+    let format = if hdr {
+        TextureFormat::Rgba16Float
+    } else {
+        TextureFormat::Rgba8UnormSrgb
+    };
+
+    let mut cap = Image::new_fill(
+        Extent3d {
+            width: CAP_WIDTH,
+            height: CAP_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        if hdr { &[0; 8] } else { &[0; 4] },
+        format,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+
+    let cell = region.size() / Vec2::new(CAP_WIDTH as f32, CAP_HEIGHT as f32);
+    for y in 0..CAP_HEIGHT {
+        for x in 0..CAP_WIDTH {
+            let mut sum = Vec4::ZERO;
+            for sy in 0..CAP_SAMPLES {
+                for sx in 0..CAP_SAMPLES {
+                    let offset = (Vec2::new(sx as f32, sy as f32) + 0.5) / CAP_SAMPLES as f32;
+                    let uv = region.min + (Vec2::new(x as f32, y as f32) + offset) * cell;
+                    let px = ((uv.x * w as f32) as u32).min(w - 1);
+                    let py = ((uv.y * h as f32) as u32).min(h - 1);
+                    if let Ok(color) = src.get_color_at(px, py) {
+                        sum += color.to_linear().to_vec4();
+                    }
+                }
+            }
+            let avg = sum / (CAP_SAMPLES * CAP_SAMPLES) as f32;
+            let _ = cap.set_color_at(x, y, Color::LinearRgba(LinearRgba::from_vec4(avg)));
+        }
+    }
+
+    cap.sampler = ImageSampler::linear();
+    Some(cap)
+}
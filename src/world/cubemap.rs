@@ -0,0 +1,473 @@
+//! Cubemap sky - CPU equirect → cubemap conversion and cube-sampling material
+//!
+//! Face order and orientation follow the GPU cube convention (+X, -X, +Y, -Y, +Z, -Z),
+//! and the equirect mapping matches `create_sky_sphere` UVs, so both sky paths line up.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::{TextureFormatPixelInfo, Volume};
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{MaterialPipeline, MaterialPipelineKey};
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    AsBindGroup, Extent3d, RenderPipelineDescriptor, SpecializedMeshPipelineError,
+    TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+use bevy::shader::ShaderRef;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::tasks::{futures::check_ready, AsyncComputeTaskPool, Task};
+use std::f32::consts::PI;
+
+use super::is_hdr_format;
+
+/// Unlit sky that samples a cube texture by the mesh's local direction,
+/// optionally crossfading into a second one (time-of-day variants)
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct SkyCubeMaterial {
+    #[texture(0, dimension = "cube")]
+    #[sampler(1)]
+    pub cubemap: Handle<Image>,
+    #[uniform(2)]
+    pub settings: SkySettings,
+    #[texture(3, dimension = "cube")]
+    pub cubemap_next: Handle<Image>,
+}
+
+#[derive(Debug, Clone, Copy, Default, bevy::render::render_resource::ShaderType)]
+pub struct SkySettings {
+    pub exposure: f32, // linear multiplier, see `exposure_scale`
+    pub blend: f32,    // 0 = `cubemap`, 1 = `cubemap_next`
+    pub _pad2: f32,
+    pub _pad3: f32,
+}
+
+impl Material for SkyCubeMaterial {
+    fn vertex_shader() -> ShaderRef {
+        "shaders/sky_cubemap.wgsl".into()
+    }
+    fn fragment_shader() -> ShaderRef {
+        "shaders/sky_cubemap.wgsl".into()
+    }
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // Viewed from inside - draw both faces like the sphere path does
+        descriptor.primitive.cull_mode = None;
+        Ok(())
+    }
+}
+
+/// Point on the unit cube for face coordinates (`u`, `v` in -1..1, v pointing down)
+pub fn cube_face_point(face: u32, u: f32, v: f32) -> Vec3 {
+    match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    }
+}
+
+/// Unit direction through a cube face at (`u`, `v`)
+pub fn cube_face_direction(face: u32, u: f32, v: f32) -> Vec3 {
+    cube_face_point(face, u, v).normalize()
+}
+
+/// Equirect texture coordinates (0..1) for a direction, matching `create_sky_sphere`
+pub fn direction_to_equirect_uv(dir: Vec3) -> Vec2 {
+    let theta = dir.z.atan2(dir.x).rem_euclid(2.0 * PI);
+    let phi = dir.y.clamp(-1.0, 1.0).acos();
+    Vec2::new(1.0 - theta / (2.0 * PI), phi / PI)
+}
+
+/// Face rows converted per frame on wasm, which has no threads to convert on
+#[cfg(target_arch = "wasm32")]
+const ROWS_PER_FRAME: u32 = 16;
+
+/// Face edge for a view of `src`: a quarter of its width, within `64..=max_face`
+pub fn cubemap_face_size(src: &Image, region: Rect, max_face: u32) -> u32 {
+    let width = src.width() as f32 * region.width();
+    (width as u32 / 4).clamp(64, max_face.max(64))
+}
+
+/// Convert an equirectangular panorama into a six-layer cube image.
+///
+/// `region` is the normalized part of `src` holding the equirect view (the whole
+/// image, or one eye of a stereo pair). Float (HDR) sources produce `Rgba16Float`
+/// faces, anything else `Rgba8UnormSrgb`. Returns `None` if the source has no
+/// CPU-side data or an unreadable format.
+pub fn equirect_to_cubemap(src: &Image, face_size: u32, region: Rect) -> Option<Image> {
+    let mut fill = CubeFill::new(src, face_size, region)?;
+    fill.fill(src, u32::MAX)?;
+    Some(fill.finish())
+}
+
+/// Equirect → cubemap conversion off the frame: a task on the compute pool,
+/// or a few face rows per frame on wasm
+pub struct CubemapJob {
+    #[cfg(not(target_arch = "wasm32"))]
+    task: Task<Option<Image>>,
+    #[cfg(target_arch = "wasm32")]
+    source: Handle<Image>,
+    #[cfg(target_arch = "wasm32")]
+    fill: Option<CubeFill>,
+}
+
+impl CubemapJob {
+    /// Start converting the `region` view of `source`; `None` if it isn't
+    /// loaded with CPU-side data
+    pub fn start(
+        images: &Assets<Image>,
+        source: &Handle<Image>,
+        region: Rect,
+        max_face: u32,
+    ) -> Option<Self> {
+        let src = images.get(source).filter(|src| src.data.is_some())?;
+        let face_size = cubemap_face_size(src, region, max_face);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let src = src.clone();
+            let task = AsyncComputeTaskPool::get()
+                .spawn(async move { equirect_to_cubemap(&src, face_size, region) });
+            Some(Self { task })
+        }
+        #[cfg(target_arch = "wasm32")]
+        Some(Self {
+            source: source.clone(),
+            fill: Some(CubeFill::new(src, face_size, region)?),
+        })
+    }
+
+    /// `Some` once finished, holding the cube if the conversion worked
+    pub fn poll(&mut self, images: &Assets<Image>) -> Option<Option<Image>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let _ = images;
+            check_ready(&mut self.task)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let Some(src) = images.get(&self.source) else {
+                return Some(None);
+            };
+            match self.fill.as_mut()?.fill(src, ROWS_PER_FRAME) {
+                Some(false) => None,
+                Some(true) => Some(self.fill.take().map(CubeFill::finish)),
+                None => Some(None),
+            }
+        }
+    }
+}
+
+/// Cube image filled a number of face rows at a time
+struct CubeFill {
+    cube: Image,
+    face_size: u32,
+    region: Rect,
+    /// Next row to convert, counted down all six faces in order
+    row: u32,
+}
+
+impl CubeFill {
+    fn new(src: &Image, face_size: u32, region: Rect) -> Option<Self> {
+        src.data.as_ref()?;
+        src.get_color_at(0, 0).ok()?;
+
+        let format = if is_hdr_format(src.texture_descriptor.format) {
+            TextureFormat::Rgba16Float
+        } else {
+            TextureFormat::Rgba8UnormSrgb
+        };
+        let pixel_size = format.pixel_size().ok()?;
+        let size = Extent3d {
+            width: face_size,
+            height: face_size,
+            depth_or_array_layers: 6,
+        };
+        let cube = Image::new(
+            size,
+            TextureDimension::D2,
+            vec![0; size.volume() * pixel_size],
+            format,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        Some(Self {
+            cube,
+            face_size,
+            region,
+            row: 0,
+        })
+    }
+
+    /// Convert up to `rows` more rows; `Some(true)` once every face is done
+    fn fill(&mut self, src: &Image, rows: u32) -> Option<bool> {
+        let size = self.face_size;
+        let region = PixelRegion::new(src, self.region);
+        let end = self.row.saturating_add(rows).min(6 * size);
+
+        for row in self.row..end {
+            let (face, y) = (row / size, row % size);
+            for x in 0..size {
+                let u = 2.0 * (x as f32 + 0.5) / size as f32 - 1.0;
+                let v = 2.0 * (y as f32 + 0.5) / size as f32 - 1.0;
+                let uv = direction_to_equirect_uv(cube_face_direction(face, u, v));
+                let color = sample_bilinear(src, &region, uv);
+                self.cube.set_color_at_3d(x, y, face, color.into()).ok()?;
+            }
+        }
+        self.row = end;
+        Some(end == 6 * size)
+    }
+
+    fn finish(mut self) -> Image {
+        self.cube.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..default()
+        });
+        self.cube
+    }
+}
+
+/// Pixel bounds of the equirect view inside the source image
+struct PixelRegion {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+impl PixelRegion {
+    fn new(src: &Image, rect: Rect) -> Self {
+        let size = src.size().as_vec2();
+        let min = (rect.min * size).round().as_ivec2();
+        let max = (rect.max * size).round().as_ivec2();
+        Self {
+            x: min.x,
+            y: min.y,
+            w: (max.x - min.x).max(1),
+            h: (max.y - min.y).max(1),
+        }
+    }
+}
+
+/// Bilinear lookup in linear space - wraps horizontally, clamps at the poles
+fn sample_bilinear(src: &Image, region: &PixelRegion, uv: Vec2) -> LinearRgba {
+    let (w, h) = (region.w, region.h);
+    let x = uv.x * w as f32 - 0.5;
+    let y = uv.y * h as f32 - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+
+    let texel = |tx: i32, ty: i32| {
+        let tx = (region.x + tx.rem_euclid(w)) as u32;
+        let ty = (region.y + ty.clamp(0, h - 1)) as u32;
+        src.get_color_at(tx, ty)
+            .map(|c| c.to_linear())
+            .unwrap_or(LinearRgba::BLACK)
+    };
+
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let top = texel(x0, y0).mix(&texel(x0 + 1, y0), fx);
+    let bottom = texel(x0, y0 + 1).mix(&texel(x0 + 1, y0 + 1), fx);
+    top.mix(&bottom, fy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FACES: [Vec3; 6] = [
+        Vec3::X,
+        Vec3::NEG_X,
+        Vec3::Y,
+        Vec3::NEG_Y,
+        Vec3::Z,
+        Vec3::NEG_Z,
+    ];
+
+    /// Inverse of `direction_to_equirect_uv`
+    fn equirect_uv_to_direction(uv: Vec2) -> Vec3 {
+        let theta = (1.0 - uv.x) * 2.0 * PI;
+        let phi = uv.y * PI;
+        Vec3::new(phi.sin() * theta.cos(), phi.cos(), phi.sin() * theta.sin())
+    }
+
+    /// Equirect with one colour band per cube face: poles on top and bottom,
+    /// four quarters around the horizon centred on +X, -Z, -X and +Z
+    fn banded_equirect(format: TextureFormat, colors: [Color; 6]) -> Image {
+        let (w, h) = (64, 32);
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: w,
+                height: h,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &vec![0; format.pixel_size().unwrap()],
+            format,
+            RenderAssetUsages::all(),
+        );
+        for y in 0..h {
+            for x in 0..w {
+                let (u, v) = ((x as f32 + 0.5) / w as f32, (y as f32 + 0.5) / h as f32);
+                let face = if v < 0.25 {
+                    2
+                } else if v > 0.75 {
+                    3
+                } else {
+                    // u = 0 → +X, 0.25 → -Z, 0.5 → -X, 0.75 → +Z
+                    [0, 5, 1, 4][((u * 4.0 + 0.5) as usize) % 4]
+                };
+                image.set_color_at(x, y, colors[face]).unwrap();
+            }
+        }
+        image
+    }
+
+    fn assert_face_centres(cube: &Image, colors: [Color; 6]) {
+        for (face, expected) in colors.iter().enumerate() {
+            let got = cube.get_color_at_3d(4, 4, face as u32).unwrap().to_linear();
+            let expected = expected.to_linear();
+            let diff = Vec3::new(got.red, got.green, got.blue)
+                - Vec3::new(expected.red, expected.green, expected.blue);
+            assert!(
+                diff.abs().max_element() < 0.01,
+                "face {face}: got {got:?}, expected {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn face_centres_point_along_axes() {
+        for (face, axis) in FACES.iter().enumerate() {
+            let dir = cube_face_direction(face as u32, 0.0, 0.0);
+            assert!(dir.abs_diff_eq(*axis, 1e-6), "face {face}: {dir}");
+        }
+    }
+
+    #[test]
+    fn face_v_points_down_except_on_poles() {
+        // v grows downwards on the side faces, towards +Z on +Y and -Z on -Y
+        for face in [0, 1, 4, 5] {
+            assert!(cube_face_direction(face, 0.0, 1.0).y < 0.0, "face {face}");
+        }
+        assert!(cube_face_direction(2, 0.0, 1.0).z > 0.0);
+        assert!(cube_face_direction(3, 0.0, 1.0).z < 0.0);
+    }
+
+    #[test]
+    fn equirect_uv_round_trips() {
+        for face in 0..6 {
+            for (u, v) in [(0.0, 0.0), (0.5, -0.3), (-0.7, 0.6), (0.9, 0.9)] {
+                let dir = cube_face_direction(face, u, v);
+                let uv = direction_to_equirect_uv(dir);
+                assert!((0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y));
+                let back = equirect_uv_to_direction(uv);
+                assert!(back.abs_diff_eq(dir, 1e-4), "face {face}: {dir} → {back}");
+            }
+        }
+    }
+
+    #[test]
+    fn equirect_axes_land_on_expected_uvs() {
+        let uv = |dir: Vec3| direction_to_equirect_uv(dir);
+        assert!((uv(Vec3::NEG_X) - Vec2::new(0.5, 0.5)).length() < 1e-6);
+        assert!((uv(Vec3::Z) - Vec2::new(0.75, 0.5)).length() < 1e-6);
+        assert!((uv(Vec3::NEG_Z) - Vec2::new(0.25, 0.5)).length() < 1e-6);
+        assert!(uv(Vec3::Y).y < 1e-6);
+        assert!(uv(Vec3::NEG_Y).y > 1.0 - 1e-6);
+    }
+
+    #[test]
+    fn srgb_equirect_converts_to_srgb_faces() {
+        let colors = [
+            Color::srgb(1.0, 0.0, 0.0),
+            Color::srgb(0.0, 1.0, 0.0),
+            Color::srgb(0.0, 0.0, 1.0),
+            Color::srgb(1.0, 1.0, 0.0),
+            Color::srgb(0.0, 1.0, 1.0),
+            Color::srgb(1.0, 0.0, 1.0),
+        ];
+        let src = banded_equirect(TextureFormat::Rgba8UnormSrgb, colors);
+        let cube = equirect_to_cubemap(&src, 8, Rect::new(0.0, 0.0, 1.0, 1.0)).unwrap();
+
+        assert_eq!(
+            cube.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(cube.texture_descriptor.size.depth_or_array_layers, 6);
+        assert_face_centres(&cube, colors);
+    }
+
+    #[test]
+    fn float_equirect_converts_to_float_faces() {
+        // Above 1.0 to check the radiance survives
+        let colors = [
+            Color::linear_rgb(4.0, 0.0, 0.0),
+            Color::linear_rgb(0.0, 2.0, 0.0),
+            Color::linear_rgb(0.0, 0.0, 8.0),
+            Color::linear_rgb(3.0, 3.0, 0.0),
+            Color::linear_rgb(0.0, 0.5, 0.5),
+            Color::linear_rgb(1.5, 0.0, 1.5),
+        ];
+        let src = banded_equirect(TextureFormat::Rgba32Float, colors);
+        let cube = equirect_to_cubemap(&src, 8, Rect::new(0.0, 0.0, 1.0, 1.0)).unwrap();
+
+        assert_eq!(cube.texture_descriptor.format, TextureFormat::Rgba16Float);
+        assert_face_centres(&cube, colors);
+    }
+
+    #[test]
+    fn linear_8bit_equirect_stays_8bit() {
+        // A PNG loaded with `is_srgb: false` is still a display-referred image
+        let colors = [
+            Color::linear_rgb(0.5, 0.0, 0.0),
+            Color::linear_rgb(0.0, 0.5, 0.0),
+            Color::linear_rgb(0.0, 0.0, 0.5),
+            Color::linear_rgb(0.5, 0.5, 0.0),
+            Color::linear_rgb(0.0, 0.5, 0.5),
+            Color::linear_rgb(0.5, 0.0, 0.5),
+        ];
+        let src = banded_equirect(TextureFormat::Rgba8Unorm, colors);
+        let cube = equirect_to_cubemap(&src, 8, Rect::new(0.0, 0.0, 1.0, 1.0)).unwrap();
+
+        assert_eq!(
+            cube.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_face_centres(&cube, colors);
+    }
+
+    #[test]
+    fn region_selects_one_eye() {
+        // Top-bottom stereo: left eye bands on top, a flat colour below
+        let colors = [Color::srgb(1.0, 0.0, 0.0); 6];
+        let eye = banded_equirect(TextureFormat::Rgba8UnormSrgb, colors);
+        let mut src = Image::new_fill(
+            Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::all(),
+        );
+        for y in 0..32 {
+            for x in 0..64 {
+                src.set_color_at(x, y, eye.get_color_at(x, y).unwrap())
+                    .unwrap();
+            }
+        }
+
+        let top = equirect_to_cubemap(&src, 8, Rect::new(0.0, 0.0, 1.0, 0.5)).unwrap();
+        assert_face_centres(&top, colors);
+        let bottom = equirect_to_cubemap(&src, 8, Rect::new(0.0, 0.5, 1.0, 1.0)).unwrap();
+        assert_face_centres(&bottom, [Color::srgb(0.0, 0.0, 1.0); 6]);
+    }
+}
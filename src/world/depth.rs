@@ -0,0 +1,127 @@
+//! Depth skies - equirect depth map displaces the sky mesh for 6DoF parallax
+//!
+//! Each sky vertex is pushed out to the distance the depth map stores for its
+//! direction, so walking produces real parallax and near scenery writes depth
+//! that correctly hides characters behind it.
+
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::f32::consts::PI;
+
+/// Higher than the flat sky so depth edges stay reasonably crisp
+const DEPTH_SECTORS: u32 = 256;
+const DEPTH_STACKS: u32 = 128;
+
+/// How depth map values map to distance
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DepthEncoding {
+    /// White = near, black = far (disparity, as most monocular estimators output)
+    #[default]
+    Inverse,
+    /// Black = near, white = far
+    Linear,
+}
+
+/// Depth map entry in the tour manifest
+#[derive(Deserialize, Clone, Debug)]
+pub struct DepthFile {
+    pub map: String,
+    #[serde(default)]
+    pub encoding: DepthEncoding,
+    #[serde(default = "default_near")]
+    pub near: f32,
+    #[serde(default = "default_far")]
+    pub far: f32,
+    /// Camera height the panorama was shot from (sky centre sits at this height)
+    #[serde(default = "default_capture_height")]
+    pub capture_height: f32,
+}
+
+fn default_near() -> f32 {
+    1.0
+}
+
+fn default_far() -> f32 {
+    80.0
+}
+
+fn default_capture_height() -> f32 {
+    1.7
+}
+
+/// Depth map settings (the map itself is streamed with the room)
+#[derive(Clone, Debug)]
+pub struct RoomDepth {
+    pub map: String,
+    pub encoding: DepthEncoding,
+    pub near: f32,
+    pub far: f32,
+    pub capture_height: f32,
+}
+
+impl RoomDepth {
+    /// Distance (m) for a stored depth value in 0..1
+    pub fn distance(&self, value: f32) -> f32 {
+        let value = value.clamp(0.0, 1.0);
+        match self.encoding {
+            DepthEncoding::Linear => self.near + value * (self.far - self.near),
+            DepthEncoding::Inverse => {
+                1.0 / (1.0 / self.far + value * (1.0 / self.near - 1.0 / self.far))
+            }
+        }
+    }
+}
+
+/// Sky sphere with every vertex at its depth-map distance (same UVs as `create_sky_sphere`)
+pub fn create_depth_sky_sphere(depth: &RoomDepth, map: &Image) -> Mesh {
+    let (sec, stk) = (DEPTH_SECTORS, DEPTH_STACKS);
+    let (w, h) = (map.width(), map.height());
+    let srgb = map.texture_descriptor.format.is_srgb();
+
+    // Stored value (not the decoded colour) - depth maps are data, not images
+    let sample = |u: f32, v: f32| {
+        let x = ((u * w as f32) as u32).min(w - 1);
+        let y = ((v * h as f32) as u32).min(h - 1);
+        map.get_color_at(x, y)
+            .map(|c| {
+                if srgb {
+                    c.to_srgba().red
+                } else {
+                    c.to_linear().red
+                }
+            })
+            .unwrap_or(0.0)
+    };
+
+    let mut pos = Vec::new();
+    let mut nrm = Vec::new();
+    let mut uv = Vec::new();
+    let mut idx = Vec::new();
+
+    for i in 0..=stk {
+        let v = i as f32 / stk as f32;
+        let phi = PI * v;
+        for j in 0..=sec {
+            let u = j as f32 / sec as f32;
+            let th = 2.0 * PI * u;
+            let dir = Vec3::new(phi.sin() * th.cos(), phi.cos(), phi.sin() * th.sin());
+            let r = depth.distance(sample(1.0 - u, v));
+            pos.push((dir * r).to_array());
+            nrm.push((-dir).to_array());
+            uv.push([1.0 - u, v]);
+        }
+    }
+    for i in 0..stk {
+        for j in 0..sec {
+            let a = i * (sec + 1) + j;
+            let b = a + sec + 1;
+            idx.extend([a, a + 1, b, b, a + 1, b + 1]);
+        }
+    }
+    Mesh::new(PrimitiveTopology::TriangleList, default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, pos)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, nrm)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uv)
+        .with_inserted_indices(Indices::U32(idx))
+}
@@ -0,0 +1,97 @@
+//! GPano metadata - Photo Sphere XMP (pose and cropped area) embedded in JPEGs
+//!
+//! Capture apps write the camera pose and, for partial panoramas, which part of
+//! the full 360×180 sphere the image covers. Skies are rotated by the pose and
+//! built only over the covered area, so rooms line up without manual tweaking.
+
+use bevy::prelude::*;
+
+/// Photo Sphere metadata read from a panorama's XMP packet
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GPano {
+    /// Compass heading (degrees) of the panorama centre, if the camera recorded one
+    pub heading: Option<f32>,
+    /// Degrees the panorama centre points above the horizon
+    pub pitch: f32,
+    /// Degrees the horizon is rotated counterclockwise in the image
+    pub roll: f32,
+    /// Part of the full panorama the image covers (normalized UV rect), if cropped
+    pub crop: Option<Rect>,
+}
+
+impl GPano {
+    /// Parse GPano properties from raw image bytes; `None` without a Photo Sphere packet
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let xmp = xmp_packet(bytes)?;
+        let num = |name: &str| property(xmp, name).and_then(|v| v.trim().parse::<f32>().ok());
+
+        let heading = num("PoseHeadingDegrees");
+        let pitch = num("PosePitchDegrees");
+        let roll = num("PoseRollDegrees");
+
+        let crop = (|| {
+            let full = Vec2::new(num("FullPanoWidthPixels")?, num("FullPanoHeightPixels")?);
+            let min = Vec2::new(num("CroppedAreaLeftPixels")?, num("CroppedAreaTopPixels")?);
+            let size = Vec2::new(
+                num("CroppedAreaImageWidthPixels")?,
+                num("CroppedAreaImageHeightPixels")?,
+            );
+            if full.min_element() <= 0.0 || size.min_element() <= 0.0 {
+                return None;
+            }
+            let rect = Rect::from_corners(min / full, (min + size) / full);
+            // Captures that cover the full sphere carry the tags too
+            let full_cover =
+                rect.min.abs_diff_eq(Vec2::ZERO, 1e-3) && rect.max.abs_diff_eq(Vec2::ONE, 1e-3);
+            (!full_cover).then_some(rect)
+        })();
+
+        if heading.is_none() && pitch.is_none() && roll.is_none() && crop.is_none() {
+            return None;
+        }
+        Some(Self {
+            heading,
+            pitch: pitch.unwrap_or(0.0),
+            roll: roll.unwrap_or(0.0),
+            crop,
+        })
+    }
+
+    /// Sky rotation that levels the horizon and puts compass north at -Z
+    ///
+    /// The sky sphere maps the image centre to -X; roll and pitch are undone
+    /// around that view axis, then heading turns the centre to its bearing.
+    pub fn rotation(&self) -> Quat {
+        let yaw = self.heading.map_or(0.0, |h| (270.0 - h).to_radians());
+        Quat::from_rotation_y(yaw)
+            * Quat::from_rotation_z(-self.pitch.to_radians())
+            * Quat::from_rotation_x(self.roll.to_radians())
+    }
+}
+
+/// The `<x:xmpmeta>` packet, if the file has one
+fn xmp_packet(bytes: &[u8]) -> Option<&str> {
+    let start = find(bytes, b"<x:xmpmeta")?;
+    let end = start + find(&bytes[start..], b"</x:xmpmeta>")?;
+    std::str::from_utf8(&bytes[start..end]).ok()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// A GPano property written either as an attribute or as an element
+fn property<'a>(xmp: &'a str, name: &str) -> Option<&'a str> {
+    let attr = format!("GPano:{name}=");
+    if let Some(at) = xmp.find(&attr) {
+        let rest = &xmp[at + attr.len()..];
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let rest = &rest[1..];
+        return rest.find(quote).map(|end| &rest[..end]);
+    }
+
+    let open = format!("<GPano:{name}>");
+    let at = xmp.find(&open)? + open.len();
+    let end = xmp[at..].find('<')?;
+    Some(&xmp[at..at + end])
+}
@@ -1,45 +1,89 @@
 //! World module - Room setup, skyboxes, lighting, rotation effects
 
+mod coverage;
+mod cubemap;
+mod depth;
+mod gpano;
+mod stereo;
+mod tiles;
+mod time_of_day;
+
+pub use coverage::*;
+pub use cubemap::*;
+pub use depth::*;
+pub use gpano::*;
+pub use stereo::*;
+pub use tiles::*;
+pub use time_of_day::*;
+
 use bevy::camera::visibility::RenderLayers;
+use bevy::math::Affine2;
 use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use serde::Deserialize;
 use std::f32::consts::PI;
 
-use crate::loading::PanoramaAssets;
 use crate::player::PlayerState;
+use crate::room_streaming::RoomStreaming;
+use crate::tour::Tour;
 use crate::GameState;
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(WorldConfig::default())
+        app.add_plugins(MaterialPlugin::<SkyCubeMaterial>::default())
+            .insert_resource(WorldConfig::default())
             .insert_resource(SkyboxRotation::default())
+            .init_resource::<TiledSkyState>()
             .add_systems(OnEnter(GameState::Viewing), setup_world)
             .add_systems(
                 Update,
-                (skybox_rotation_input, rotate_skybox)
-                    .chain()
+                (
+                    (
+                        sync_room_skies,
+                        finish_cubemap_skies,
+                        pulse_sky_placeholders,
+                    ),
+                    (skybox_rotation_input, rotate_skybox).chain(),
+                    (sky_exposure_input, apply_sky_exposure).chain(),
+                    stream_sky_tiles,
+                    (world_clock_input, advance_world_clock, blend_sky_variants).chain(),
+                )
                     .run_if(in_state(GameState::Viewing)),
             );
     }
 }
 
 pub const ROOM_OFFSET: f32 = 500.0;
-pub const TOTAL_ROOMS: usize = 3;
 
 #[derive(Resource)]
 pub struct WorldConfig {
     pub sky_sphere_radius: f32,
+    pub max_cubemap_face: u32,
+    pub sky_exposure: f32, // EV offset on top of each room's manifest exposure
 }
 
 impl Default for WorldConfig {
     fn default() -> Self {
         Self {
             sky_sphere_radius: 80.0,
+            max_cubemap_face: 1024,
+            sky_exposure: 0.0,
         }
     }
 }
 
+/// How a room's equirect panorama is put on the sky
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SkyProjection {
+    /// Texture wrapped directly on the UV sphere (pinches at the poles)
+    Sphere,
+    /// Converted to a cubemap on load and sampled by direction
+    #[default]
+    Cubemap,
+}
+
 /// Skybox rotation state - Ctrl+R to toggle, Ctrl+1/2/3/4 for speed
 #[derive(Resource)]
 pub struct SkyboxRotation {
@@ -63,25 +107,57 @@ impl Default for SkyboxRotation {
 #[derive(Component)]
 pub struct Skybox {
     pub room: usize,
+    pub pose: Quat, // capture pose correction from GPano metadata
+}
+
+/// Loading stand-in for a room whose panorama isn't streamed in yet
+#[derive(Component)]
+pub struct SkyPlaceholder {
+    pub room: usize,
+}
+
+/// Shared sky sphere and placeholder material, created with the world
+#[derive(Resource)]
+struct RoomSkies {
+    mesh: Handle<Mesh>,
+    placeholder: Handle<StandardMaterial>,
 }
 
 pub fn room_center(room: usize) -> Vec3 {
     Vec3::new(room as f32 * ROOM_OFFSET, 0.0, 0.0)
 }
 
+/// Linear multiplier for an exposure in EV stops
+pub fn exposure_scale(ev: f32) -> f32 {
+    2f32.powf(ev)
+}
+
+/// Float formats carry scene-referred radiance (HDR / EXR); everything else is
+/// a display-referred image, whether or not it's tagged sRGB
+pub fn is_hdr_format(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::Rgba16Float | TextureFormat::Rgba32Float | TextureFormat::Rgb9e5Ufloat
+    )
+}
+
 fn setup_world(
     mut cmd: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut mats: ResMut<Assets<StandardMaterial>>,
-    pano: Res<PanoramaAssets>,
+    tour: Res<Tour>,
     config: Res<WorldConfig>,
 ) {
-    let panos = [
-        &pano.demo_panorama,
-        &pano.demo2_panorama,
-        &pano.demo3_panorama,
-    ];
-    let sky_mesh = meshes.add(create_sky_sphere(config.sky_sphere_radius));
+    cmd.insert_resource(WorldClock::from(tour.clock));
+    cmd.insert_resource(RoomSkies {
+        mesh: meshes.add(create_sky_sphere(config.sky_sphere_radius)),
+        placeholder: mats.add(StandardMaterial {
+            base_color: Color::srgb(0.05, 0.08, 0.15),
+            unlit: true,
+            cull_mode: None,
+            ..default()
+        }),
+    });
 
     // Enhanced lighting for modern look
     cmd.spawn((
@@ -90,7 +166,7 @@ fn setup_world(
             brightness: 600.0,
             ..default()
         },
-        RenderLayers::from_layers(&[0, 1, 2]),
+        tour.all_layers(),
     ));
     cmd.spawn((
         DirectionalLight {
@@ -100,27 +176,246 @@ fn setup_world(
             ..default()
         },
         Transform::from_xyz(5.0, 10.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
-        RenderLayers::from_layers(&[0, 1, 2]),
+        tour.all_layers(),
     ));
+    info!("🌍 World: {} rooms", tour.room_count());
+}
 
-    // Skyboxes for each room
-    for (room, pano) in panos.iter().enumerate().take(TOTAL_ROOMS) {
+/// Build skies for rooms as they finish streaming in, and swap unloaded rooms
+/// back to a placeholder (portals looking into them show it)
+#[allow(clippy::too_many_arguments)]
+fn sync_room_skies(
+    mut cmd: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    tour: Res<Tour>,
+    config: Res<WorldConfig>,
+    clock: Res<WorldClock>,
+    streaming: Res<RoomStreaming>,
+    room_skies: Res<RoomSkies>,
+    skies: Query<(Entity, &Skybox)>,
+    placeholders: Query<(Entity, &SkyPlaceholder)>,
+) {
+    let sky_mesh = room_skies.mesh.clone();
+
+    // Skyboxes for each room (one per eye for stereo panoramas)
+    for (room, def) in tour.rooms.iter().enumerate() {
         let center = room_center(room);
-        cmd.spawn((
-            Mesh3d(sky_mesh.clone()),
-            MeshMaterial3d(mats.add(StandardMaterial {
-                base_color_texture: Some((*pano).clone()),
+        let built = skies.iter().any(|(_, sky)| sky.room == room);
+
+        let Some(assets) = streaming.assets(room) else {
+            // Caps and tiles are children and go with their sky
+            for (entity, _) in skies.iter().filter(|(_, sky)| sky.room == room) {
+                cmd.entity(entity).despawn();
+            }
+            if !placeholders.iter().any(|(_, p)| p.room == room) {
+                cmd.spawn((
+                    Mesh3d(sky_mesh.clone()),
+                    MeshMaterial3d(room_skies.placeholder.clone()),
+                    Transform::from_translation(center),
+                    RenderLayers::layer(room),
+                    SkyPlaceholder { room },
+                ));
+            }
+            continue;
+        };
+        if built {
+            continue;
+        }
+        for (entity, _) in placeholders.iter().filter(|(_, p)| p.room == room) {
+            cmd.entity(entity).despawn();
+        }
+
+        // Time-of-day rooms open on the variant for the current hour (video
+        // skies keep swapping their own frames)
+        let hours: Vec<f32> = def.times.iter().map(|v| v.hour).collect();
+        let variant = clock
+            .blend(&hours)
+            .map(|(a, _, _)| a)
+            .filter(|_| def.video.is_none());
+        let panorama = variant
+            .and_then(|i| assets.variants.get(i))
+            .unwrap_or(&assets.panorama)
+            .clone();
+
+        let pose = assets
+            .gpano
+            .map_or(Quat::IDENTITY, |gpano| gpano.rotation());
+
+        // Depth rooms get a displaced mesh centred at the capture height,
+        // partial panoramas only cover the part of the sky they were shot for
+        let depth = def
+            .depth
+            .as_ref()
+            .zip(assets.depth.as_ref())
+            .and_then(|(depth, map)| Some((depth, images.get(map)?)));
+        let coverage = def
+            .coverage
+            .or_else(|| assets.gpano.and_then(|g| g.crop).map(SkyCoverage::cropped))
+            .filter(|_| depth.is_none());
+        let (room_mesh, center) = match (depth, coverage) {
+            (Some((depth, map)), _) => (
+                meshes.add(create_depth_sky_sphere(depth, map)),
+                center + Vec3::Y * depth.capture_height,
+            ),
+            (None, Some(coverage)) => (
+                meshes.add(coverage.create_mesh(config.sky_sphere_radius)),
+                center,
+            ),
+            (None, None) => (sky_mesh.clone(), center),
+        };
+
+        let eyes: &[Option<Eye>] = if def.stereo.is_stereo() {
+            &[Some(Eye::Left), Some(Eye::Right)]
+        } else {
+            &[None]
+        };
+
+        for &eye in eyes {
+            let layers = match eye {
+                Some(eye) => RenderLayers::layer(eye_layer(room, eye)),
+                None => RenderLayers::layer(room),
+            };
+            let region = def.stereo.eye_region(eye.unwrap_or(Eye::Left));
+
+            let mut sky = cmd.spawn((
+                Mesh3d(room_mesh.clone()),
+                Transform::from_translation(center).with_rotation(pose),
+                layers.clone(),
+                Skybox { room, pose },
+            ));
+
+            // Video skies swap equirect frames on the sphere material, and the
+            // cubemap conversion assumes a full panorama. It runs in the
+            // background while the sphere material shows the panorama.
+            let cube_sky =
+                def.sky == SkyProjection::Cubemap && def.video.is_none() && coverage.is_none();
+            if cube_sky {
+                match CubemapJob::start(&images, &panorama, region, config.max_cubemap_face) {
+                    Some(job) => {
+                        sky.insert(PendingCubemap { job, variant });
+                    }
+                    None => warn!("⚠️ {}: cubemap conversion failed, using sphere", def.name),
+                }
+            }
+
+            let exposure = exposure_scale(def.exposure + config.sky_exposure);
+            sky.insert(MeshMaterial3d(mats.add(StandardMaterial {
+                base_color: LinearRgba::rgb(exposure, exposure, exposure).into(),
+                base_color_texture: Some(panorama.clone()),
+                uv_transform: Affine2::from_scale_angle_translation(region.size(), 0.0, region.min),
                 unlit: true,
                 double_sided: true,
                 cull_mode: None,
                 ..default()
-            })),
-            Transform::from_translation(center),
-            RenderLayers::layer(room),
-            Skybox { room },
-        ));
+            })));
+
+            // Cap sphere behind partial panoramas
+            if let Some(coverage) = coverage {
+                let (tint, texture) = match coverage.cap {
+                    CapFill::Color(r, g, b) => (LinearRgba::rgb(r, g, b), None),
+                    CapFill::Blur => (
+                        LinearRgba::WHITE,
+                        images
+                            .get(&panorama)
+                            .and_then(|src| create_blurred_cap(src, region)),
+                    ),
+                };
+                sky.with_child((
+                    Mesh3d(sky_mesh.clone()),
+                    MeshMaterial3d(mats.add(StandardMaterial {
+                        base_color: (tint * exposure).into(),
+                        base_color_texture: texture.map(|image| images.add(image)),
+                        uv_transform: coverage.cap_uv_transform(),
+                        unlit: true,
+                        double_sided: true,
+                        cull_mode: None,
+                        ..default()
+                    })),
+                    Transform::default(),
+                    layers,
+                    SkyCapFill { room, tint },
+                ));
+            }
+            if variant.is_some() {
+                sky.insert(SkyVariants::new(hours.clone(), region));
+            }
+        }
+        info!("🌍 Sky ready: {}", def.name);
+    }
+}
+
+/// Cubemap sky still converting; its sphere material shows the panorama meanwhile
+#[derive(Component)]
+struct PendingCubemap {
+    job: CubemapJob,
+    /// Time-of-day variant being converted
+    variant: Option<usize>,
+}
+
+/// Swap converted skies from the sphere material to their cubemap
+#[allow(clippy::type_complexity)]
+fn finish_cubemap_skies(
+    mut cmd: Commands,
+    mut cube_mats: ResMut<Assets<SkyCubeMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    tour: Res<Tour>,
+    config: Res<WorldConfig>,
+    mut skies: Query<
+        (
+            Entity,
+            &Skybox,
+            &mut PendingCubemap,
+            Option<&mut SkyVariants>,
+        ),
+        With<MeshMaterial3d<StandardMaterial>>,
+    >,
+) {
+    for (entity, skybox, mut pending, variants) in skies.iter_mut() {
+        let Some(done) = pending.job.poll(&images) else {
+            continue;
+        };
+        let mut sky = cmd.entity(entity);
+        sky.remove::<PendingCubemap>();
+        let def = &tour.rooms[skybox.room];
+        let Some(cube) = done else {
+            warn!("⚠️ {}: cubemap conversion failed, using sphere", def.name);
+            continue;
+        };
+
+        let cube = images.add(cube);
+        if let (Some(mut variants), Some(i)) = (variants, pending.variant) {
+            variants.cubes[i] = Some(cube.clone());
+            variants.shown = None; // Pick up the clock's pair as a cubemap sky
+        }
+        let exposure = exposure_scale(def.exposure + config.sky_exposure);
+        sky.remove::<MeshMaterial3d<StandardMaterial>>()
+            .insert(MeshMaterial3d(cube_mats.add(SkyCubeMaterial {
+                cubemap: cube.clone(),
+                settings: SkySettings {
+                    exposure,
+                    ..default()
+                },
+                cubemap_next: cube,
+            })));
+    }
+}
+
+/// Slow breathing glow so unloaded rooms read as "loading" through portals
+fn pulse_sky_placeholders(
+    time: Res<Time>,
+    room_skies: Res<RoomSkies>,
+    placeholders: Query<(), With<SkyPlaceholder>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+) {
+    if placeholders.is_empty() {
+        return;
+    }
+    let glow = 0.75 + 0.25 * (time.elapsed_secs() * 2.0).sin();
+    if let Some(mat) = mats.get_mut(&room_skies.placeholder) {
+        mat.base_color = Color::srgb(0.05 * glow, 0.08 * glow, 0.15 * glow);
     }
-    info!("🌍 World: {} rooms created", TOTAL_ROOMS);
 }
 
 fn skybox_rotation_input(keys: Res<ButtonInput<KeyCode>>, mut rotation: ResMut<SkyboxRotation>) {
@@ -160,6 +455,69 @@ fn skybox_rotation_input(keys: Res<ButtonInput<KeyCode>>, mut rotation: ResMut<S
     }
 }
 
+/// Ctrl+[ / Ctrl+] darken or brighten every sky by half a stop
+fn sky_exposure_input(keys: Res<ButtonInput<KeyCode>>, mut config: ResMut<WorldConfig>) {
+    let ctrl = keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
+    if !ctrl {
+        return;
+    }
+
+    let step = if keys.just_pressed(KeyCode::BracketRight) {
+        0.5
+    } else if keys.just_pressed(KeyCode::BracketLeft) {
+        -0.5
+    } else {
+        return;
+    };
+    config.sky_exposure = (config.sky_exposure + step).clamp(-6.0, 6.0);
+    info!("☀️ Sky exposure: {:+.1} EV", config.sky_exposure);
+}
+
+fn apply_sky_exposure(
+    config: Res<WorldConfig>,
+    tour: Res<Tour>,
+    skyboxes: Query<(
+        &Skybox,
+        Option<&MeshMaterial3d<SkyCubeMaterial>>,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+    )>,
+    tiles: Query<(&SkyTile, &MeshMaterial3d<StandardMaterial>)>,
+    caps: Query<(&SkyCapFill, &MeshMaterial3d<StandardMaterial>)>,
+    mut cube_mats: ResMut<Assets<SkyCubeMaterial>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+) {
+    if !config.is_changed() {
+        return;
+    }
+
+    for (skybox, cube, sphere) in skyboxes.iter() {
+        let Some(def) = tour.rooms.get(skybox.room) else {
+            continue;
+        };
+        let exposure = exposure_scale(def.exposure + config.sky_exposure);
+        if let Some(mat) = cube.and_then(|h| cube_mats.get_mut(&h.0)) {
+            mat.settings.exposure = exposure;
+        }
+        if let Some(mat) = sphere.and_then(|h| mats.get_mut(&h.0)) {
+            mat.base_color = LinearRgba::rgb(exposure, exposure, exposure).into();
+        }
+    }
+
+    for (tile, handle) in tiles.iter() {
+        let exposure = exposure_scale(tour.rooms[tile.room].exposure + config.sky_exposure);
+        if let Some(mat) = mats.get_mut(&handle.0) {
+            mat.base_color = LinearRgba::rgb(exposure, exposure, exposure).into();
+        }
+    }
+
+    for (cap, handle) in caps.iter() {
+        let exposure = exposure_scale(tour.rooms[cap.room].exposure + config.sky_exposure);
+        if let Some(mat) = mats.get_mut(&handle.0) {
+            mat.base_color = (cap.tint * exposure).into();
+        }
+    }
+}
+
 fn rotate_skybox(
     time: Res<Time>,
     mut rotation: ResMut<SkyboxRotation>,
@@ -177,26 +535,34 @@ fn rotate_skybox(
 
     for (mut transform, skybox) in skyboxes.iter_mut() {
         if skybox.room == player.room {
-            let center = room_center(skybox.room);
-            transform.translation = center;
-            transform.rotation = Quat::from_rotation_y(rotation.angle);
+            transform.rotation = Quat::from_rotation_y(rotation.angle) * skybox.pose;
         }
     }
 }
 
 pub fn create_sky_sphere(r: f32) -> Mesh {
+    create_partial_sky_sphere(r, Rect::new(0.0, 0.0, 1.0, 1.0))
+}
+
+/// Sky sphere patch for the part of the equirect panorama `coverage` (normalized
+/// UV rect) describes, with its own UVs spanning 0..1 across the image
+pub fn create_partial_sky_sphere(r: f32, coverage: Rect) -> Mesh {
     use bevy::mesh::{Indices, PrimitiveTopology};
-    let (sec, stk) = (96u32, 48u32); // Higher resolution for smoother look
+    // Higher resolution for smoother look, scaled down with the covered area
+    let sec = ((96.0 * coverage.width()).ceil() as u32).max(4);
+    let stk = ((48.0 * coverage.height()).ceil() as u32).max(2);
     let mut pos = Vec::new();
     let mut nrm = Vec::new();
     let mut uv = Vec::new();
     let mut idx = Vec::new();
 
     for i in 0..=stk {
-        let v = i as f32 / stk as f32;
+        let t = i as f32 / stk as f32;
+        let v = coverage.min.y + t * coverage.height();
         let phi = PI * v;
         for j in 0..=sec {
-            let u = j as f32 / sec as f32;
+            let s = j as f32 / sec as f32;
+            let u = 1.0 - coverage.max.x + s * coverage.width();
             let th = 2.0 * PI * u;
             let (x, y, z) = (
                 r * phi.sin() * th.cos(),
@@ -205,7 +571,7 @@ pub fn create_sky_sphere(r: f32) -> Mesh {
             );
             pos.push([x, y, z]);
             nrm.push([-x / r, -y / r, -z / r]);
-            uv.push([1.0 - u, v]);
+            uv.push([1.0 - s, t]);
         }
     }
     for i in 0..stk {
@@ -0,0 +1,76 @@
+//! Stereo panoramas - per-eye halves of a 360 image on per-eye render layers
+//!
+//! Mono skies live on `RenderLayers::layer(room)`. A stereo room splits its sky
+//! into a left and right sphere on `eye_layer(room, eye)`; each camera renders
+//! its room layer plus the layer of the eye it represents: the XR view index
+//! in VR, the left or right half of the canvas in WebXR. Desktop and portal
+//! cameras use the left eye. Views from inside a room also render the room's
+//! door surfaces (see `portals::portal_layer`).
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::portals::portal_layer;
+
+/// Most rooms a tour can hold: room, per-eye and portal layers each take a
+/// band of render layers sized for this many rooms
+pub const MAX_ROOMS: usize = 256;
+/// First render layer used for per-eye skies (room layers stay below it)
+pub const EYE_LAYER_BASE: usize = MAX_ROOMS;
+
+/// How the two eye views are packed into one panorama image
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StereoLayout {
+    #[default]
+    Mono,
+    /// Left eye on top, right eye below (each 2:1)
+    TopBottom,
+    /// Left eye on the left, right eye on the right (each 2:1)
+    SideBySide,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+impl Eye {
+    /// OpenXR view index → eye (view 0 is the left eye)
+    pub fn from_view(index: u32) -> Self {
+        if index == 0 {
+            Eye::Left
+        } else {
+            Eye::Right
+        }
+    }
+}
+
+impl StereoLayout {
+    pub fn is_stereo(self) -> bool {
+        self != StereoLayout::Mono
+    }
+
+    /// Part of the image (normalized UV rect) holding an eye's equirect view
+    pub fn eye_region(self, eye: Eye) -> Rect {
+        let second = eye == Eye::Right;
+        match self {
+            StereoLayout::Mono => Rect::new(0.0, 0.0, 1.0, 1.0),
+            StereoLayout::TopBottom if second => Rect::new(0.0, 0.5, 1.0, 1.0),
+            StereoLayout::TopBottom => Rect::new(0.0, 0.0, 1.0, 0.5),
+            StereoLayout::SideBySide if second => Rect::new(0.5, 0.0, 1.0, 1.0),
+            StereoLayout::SideBySide => Rect::new(0.0, 0.0, 0.5, 1.0),
+        }
+    }
+}
+
+/// Render layer for one eye's sky in a stereo room
+pub fn eye_layer(room: usize, eye: Eye) -> usize {
+    EYE_LAYER_BASE + room * 2 + (eye == Eye::Right) as usize
+}
+
+/// Layers a camera looking into `room` with `eye` should render
+pub fn room_view_layers(room: usize, eye: Eye) -> RenderLayers {
+    RenderLayers::from_layers(&[room, eye_layer(room, eye), portal_layer(room, 1)])
+}
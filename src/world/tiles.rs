@@ -0,0 +1,280 @@
+//! Tiled sky - multi-resolution cubemap pyramid streamed around the view
+//!
+//! Level `L` splits each cube face into `2^L × 2^L` tiles of `tile_size` pixels.
+//! Tiles are sphere patches just inside the sky sphere, parented to the room's
+//! `Skybox` (so they follow its rotation); finer levels sit slightly closer and
+//! draw over coarser ones. The room panorama stays underneath while tiles load.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::camera::visibility::RenderLayers;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+use super::{cube_face_direction, cube_face_point, exposure_scale, Skybox, WorldConfig};
+use crate::camera::{CameraState, GameCamera};
+use crate::performance::QualitySettings;
+use crate::player::PlayerState;
+use crate::tour::Tour;
+
+/// Cube face names used in tile paths, in GPU cube order
+pub const FACE_NAMES: [&str; 6] = ["px", "nx", "py", "ny", "pz", "nz"];
+
+/// Tile pyramid declared for a room in the tour manifest
+#[derive(Deserialize, Clone, Debug)]
+pub struct TileConfig {
+    /// Path with `{level}`, `{face}` (px/nx/py/ny/pz/nz), `{x}` and `{y}` placeholders
+    pub path: String,
+    #[serde(default = "default_tile_size")]
+    pub tile_size: u32,
+    /// Number of levels; level 0 is one tile per face
+    pub levels: u32,
+}
+
+fn default_tile_size() -> u32 {
+    512
+}
+
+impl TileConfig {
+    pub fn tile_path(&self, key: TileKey) -> String {
+        self.path
+            .replace("{level}", &key.level.to_string())
+            .replace("{face}", FACE_NAMES[key.face as usize])
+            .replace("{x}", &key.x.to_string())
+            .replace("{y}", &key.y.to_string())
+    }
+
+    /// GPU memory of one decoded tile (RGBA8)
+    fn tile_bytes(&self) -> usize {
+        (self.tile_size * self.tile_size * 4) as usize
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TileKey {
+    pub level: u32,
+    pub face: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl TileKey {
+    /// Face-space rect (-1..1, v down) covered by the tile
+    fn face_rect(self) -> Rect {
+        let n = (1u32 << self.level) as f32;
+        let min = Vec2::new(self.x as f32, self.y as f32) / n * 2.0 - 1.0;
+        Rect::from_corners(min, min + Vec2::splat(2.0 / n))
+    }
+}
+
+/// Streaming state for the current room's tiles
+#[derive(Resource, Default)]
+pub struct TiledSkyState {
+    room: Option<usize>,
+    pending: HashMap<TileKey, Handle<Image>>,
+    shown: HashMap<TileKey, Vec<Entity>>,
+}
+
+#[derive(Component)]
+pub struct SkyTile {
+    pub room: usize,
+}
+
+/// Grid steps per side of a level 0 tile; halved per level, at least 2
+const TILE_STEPS: u32 = 16;
+
+/// Radius tiles sit at - just inside the sky sphere's facets so they cover it
+/// from anywhere in the room without parallax against it
+fn tile_radius(config: &WorldConfig) -> f32 {
+    config.sky_sphere_radius * 0.995
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn stream_sky_tiles(
+    mut cmd: Commands,
+    mut state: ResMut<TiledSkyState>,
+    tour: Res<Tour>,
+    player: Res<PlayerState>,
+    camera_state: Res<CameraState>,
+    quality: Res<QualitySettings>,
+    config: Res<WorldConfig>,
+    asset_server: Res<AssetServer>,
+    cameras: Query<(&GlobalTransform, &Camera), With<GameCamera>>,
+    skies: Query<(Entity, &Skybox, &Transform, &RenderLayers)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+) {
+    // Drop everything when leaving a tiled room
+    if state.room != Some(player.room) {
+        for entity in state.shown.drain().flat_map(|(_, e)| e) {
+            cmd.entity(entity).despawn();
+        }
+        state.pending.clear();
+        state.room = Some(player.room);
+    }
+
+    let Some(tiles) = &tour.rooms[player.room].tiles else {
+        return;
+    };
+    let Ok((cam_transform, camera)) = cameras.single() else {
+        return;
+    };
+    let room_skies: Vec<_> = skies
+        .iter()
+        .filter(|(_, sky, _, _)| sky.room == player.room)
+        .collect();
+    let Some((_, _, sky_transform, _)) = room_skies.first() else {
+        return;
+    };
+
+    // Camera view in sky-local space
+    let forward = sky_transform.rotation.inverse() * cam_transform.forward().as_vec3();
+    let viewport = camera
+        .physical_viewport_size()
+        .unwrap_or(UVec2::new(1280, 720));
+    let aspect = viewport.x as f32 / viewport.y.max(1) as f32;
+    let half_fov = camera_state.fov.to_radians() / 2.0;
+    let half_diag = (half_fov.tan() * (1.0 + aspect * aspect).sqrt()).atan();
+
+    // Finest level whose texel density reaches the screen's pixels per radian
+    let screen_density = viewport.y as f32 / (2.0 * half_fov);
+    let target_level = (0..tiles.levels)
+        .find(|&level| (tiles.tile_size << level) as f32 / FRAC_PI_2 >= screen_density)
+        .unwrap_or(tiles.levels.saturating_sub(1));
+
+    // Visible tiles, coarse levels first so there's always a fallback
+    let mut wanted = Vec::new();
+    for level in 0..=target_level {
+        let n = 1u32 << level;
+        let radius = FRAC_PI_4 / n as f32 * std::f32::consts::SQRT_2;
+        let mut visible: Vec<(f32, TileKey)> = (0..6)
+            .flat_map(|face| (0..n).flat_map(move |y| (0..n).map(move |x| (face, x, y))))
+            .filter_map(|(face, x, y)| {
+                let key = TileKey { level, face, x, y };
+                let center = key.face_rect().center();
+                let dir = cube_face_point(face, center.x, center.y).normalize();
+                let angle = dir.dot(forward).clamp(-1.0, 1.0).acos();
+                (angle < half_diag + radius).then_some((angle, key))
+            })
+            .collect();
+        visible.sort_by(|a, b| a.0.total_cmp(&b.0));
+        wanted.extend(visible.into_iter().map(|(_, key)| key));
+    }
+
+    // Memory budget from quality settings; keep already-loaded tiles if there's room
+    let budget = (quality.tile_budget_mb as usize * 1024 * 1024 / tiles.tile_bytes()).max(6);
+    wanted.truncate(budget);
+    let mut loaded: Vec<TileKey> = state
+        .shown
+        .keys()
+        .filter(|key| !wanted.contains(key))
+        .copied()
+        .collect();
+    loaded.sort_by_key(|key| key.level);
+    let spare = budget.saturating_sub(wanted.len());
+    wanted.extend(loaded.into_iter().take(spare));
+
+    // Evict tiles outside the budget
+    let evicted: Vec<TileKey> = state
+        .shown
+        .keys()
+        .filter(|key| !wanted.contains(key))
+        .copied()
+        .collect();
+    for key in evicted {
+        for entity in state.shown.remove(&key).unwrap_or_default() {
+            cmd.entity(entity).despawn();
+        }
+    }
+    state.pending.retain(|key, _| wanted.contains(key));
+
+    // Request missing tiles, spawn those that finished loading
+    let radius = tile_radius(&config);
+    for key in wanted {
+        if state.shown.contains_key(&key) {
+            continue;
+        }
+        let handle = state
+            .pending
+            .entry(key)
+            .or_insert_with(|| asset_server.load(tiles.tile_path(key)))
+            .clone();
+        if !asset_server.is_loaded(&handle) {
+            continue;
+        }
+
+        let exposure = exposure_scale(tour.rooms[player.room].exposure + config.sky_exposure);
+        let mesh = meshes.add(create_tile_mesh(key, radius));
+        let material = mats.add(StandardMaterial {
+            base_color: LinearRgba::rgb(exposure, exposure, exposure).into(),
+            base_color_texture: Some(handle),
+            unlit: true,
+            double_sided: true,
+            cull_mode: None,
+            ..default()
+        });
+        let entities = room_skies
+            .iter()
+            .map(|(sky_entity, _, _, layers)| {
+                let tile = cmd
+                    .spawn((
+                        Mesh3d(mesh.clone()),
+                        MeshMaterial3d(material.clone()),
+                        Transform::default(),
+                        (*layers).clone(),
+                        SkyTile { room: player.room },
+                    ))
+                    .id();
+                cmd.entity(*sky_entity).add_child(tile);
+                tile
+            })
+            .collect();
+        state.pending.remove(&key);
+        state.shown.insert(key, entities);
+    }
+}
+
+/// Sphere patch under the tile, subdivided so it follows the sky sphere;
+/// finer levels are pulled in so they draw on top
+fn create_tile_mesh(key: TileKey, radius: f32) -> Mesh {
+    let rect = key.face_rect();
+    let r = radius * (1.0 - key.level as f32 * 0.002);
+    let steps = (TILE_STEPS >> key.level).max(2);
+
+    let mut pos = Vec::new();
+    let mut nrm = Vec::new();
+    let mut uv = Vec::new();
+    for i in 0..=steps {
+        let t = i as f32 / steps as f32;
+        let v = rect.min.y + t * rect.height();
+        for j in 0..=steps {
+            let s = j as f32 / steps as f32;
+            let u = rect.min.x + s * rect.width();
+            let dir = cube_face_direction(key.face, u, v);
+            pos.push((dir * r).to_array());
+            nrm.push((-dir).to_array());
+            uv.push([s, t]);
+        }
+    }
+
+    let mut idx = Vec::new();
+    let row = steps + 1;
+    for i in 0..steps {
+        for j in 0..steps {
+            let a = i * row + j;
+            let b = a + row;
+            idx.extend([a, a + 1, b + 1, a, b + 1, b]);
+        }
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, pos)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, nrm)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uv)
+    .with_inserted_indices(Indices::U32(idx))
+}
@@ -0,0 +1,265 @@
+//! Time of day - world clock and per-room panorama variants (dawn/day/dusk/night)
+//!
+//! Rooms may list panoramas for several hours. Cubemap skies crossfade between
+//! the two variants around the clock's hour in the sky shader, converting each
+//! variant in the background when the clock first needs it; other skies switch
+//! to the nearer one. IBL blends its per-variant analysis the same way.
+
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::{CubemapJob, SkyCubeMaterial, Skybox, WorldConfig};
+use crate::room_streaming::RoomStreaming;
+
+const DAY_HOURS: f32 = 24.0;
+
+/// Clock entry in the tour manifest
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct ClockFile {
+    /// Hour the tour opens at (accelerated clock)
+    #[serde(default = "default_start")]
+    pub start: f32,
+    /// Clock seconds per real second (60 = a day every 24 minutes)
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    /// Follow the visitor's wall clock instead
+    #[serde(default)]
+    pub real_time: bool,
+    /// Hours added to UTC for the wall clock where the browser's local time isn't available
+    #[serde(default)]
+    pub utc_offset: f32,
+}
+
+impl Default for ClockFile {
+    fn default() -> Self {
+        Self {
+            start: default_start(),
+            speed: default_speed(),
+            real_time: false,
+            utc_offset: 0.0,
+        }
+    }
+}
+
+fn default_start() -> f32 {
+    12.0
+}
+
+fn default_speed() -> f32 {
+    60.0
+}
+
+/// A room panorama shown around `hour`
+#[derive(Deserialize, Clone, Debug)]
+pub struct TimeVariant {
+    pub hour: f32,
+    pub panorama: String,
+}
+
+/// World time of day, shared by skies and lighting
+#[derive(Resource)]
+pub struct WorldClock {
+    /// 0..24
+    pub hour: f32,
+    pub speed: f32,
+    pub paused: bool,
+    pub real_time: bool,
+    pub utc_offset: f32,
+}
+
+impl From<ClockFile> for WorldClock {
+    fn from(file: ClockFile) -> Self {
+        Self {
+            hour: file.start.rem_euclid(DAY_HOURS),
+            speed: file.speed,
+            paused: false,
+            real_time: file.real_time,
+            utc_offset: file.utc_offset,
+        }
+    }
+}
+
+impl WorldClock {
+    /// Variants bracketing the current hour and how far (0..1) to fade from the first to the second
+    pub fn blend(&self, hours: &[f32]) -> Option<(usize, usize, f32)> {
+        variant_blend(hours, self.hour)
+    }
+}
+
+/// Bracketing variants for `hour` in hour-sorted `hours`, wrapping past midnight
+pub fn variant_blend(hours: &[f32], hour: f32) -> Option<(usize, usize, f32)> {
+    let n = hours.len();
+    if n < 2 {
+        return (n == 1).then_some((0, 0, 0.0));
+    }
+    let a = hours.iter().rposition(|h| *h <= hour).unwrap_or(n - 1);
+    let b = (a + 1) % n;
+    let span = (hours[b] - hours[a]).rem_euclid(DAY_HOURS).max(1e-3);
+    let t = ((hour - hours[a]).rem_euclid(DAY_HOURS) / span).clamp(0.0, 1.0);
+    Some((a, b, t * t * (3.0 - 2.0 * t)))
+}
+
+/// Local hour of the wall clock
+fn wall_clock_hour(utc_offset: f32) -> f32 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = utc_offset;
+        let now = js_sys::Date::new_0();
+        now.get_hours() as f32 + now.get_minutes() as f32 / 60.0 + now.get_seconds() as f32 / 3600.0
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |d| d.as_secs_f64() % 86_400.0) as f32;
+        (secs / 3600.0 + utc_offset).rem_euclid(DAY_HOURS)
+    }
+}
+
+pub fn advance_world_clock(time: Res<Time>, mut clock: ResMut<WorldClock>) {
+    if clock.paused {
+        return;
+    }
+    clock.hour = if clock.real_time {
+        wall_clock_hour(clock.utc_offset)
+    } else {
+        (clock.hour + time.delta_secs() * clock.speed / 3600.0).rem_euclid(DAY_HOURS)
+    };
+}
+
+/// Ctrl+, / Ctrl+. step the clock an hour, Ctrl+P pauses it
+pub fn world_clock_input(keys: Res<ButtonInput<KeyCode>>, mut clock: ResMut<WorldClock>) {
+    let ctrl = keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
+    if !ctrl {
+        return;
+    }
+    let step = if keys.just_pressed(KeyCode::Period) {
+        1.0
+    } else if keys.just_pressed(KeyCode::Comma) {
+        -1.0
+    } else {
+        0.0
+    };
+    if step != 0.0 {
+        clock.real_time = false;
+        clock.hour = (clock.hour + step).rem_euclid(DAY_HOURS);
+        info!("🕐 Clock: {:05.2}h", clock.hour);
+    }
+    if keys.just_pressed(KeyCode::KeyP) {
+        clock.paused = !clock.paused;
+        info!(
+            "🕐 Clock {}",
+            if clock.paused { "paused" } else { "running" }
+        );
+    }
+}
+
+/// Time-of-day panoramas of a sky, with cubemaps converted as the clock reaches them
+#[derive(Component)]
+pub struct SkyVariants {
+    pub hours: Vec<f32>,
+    /// Normalized part of each image holding this sky's view (one eye of a stereo pair)
+    pub region: Rect,
+    pub cubes: Vec<Option<Handle<Image>>>,
+    /// Conversions still running, by variant
+    pub jobs: Vec<Option<CubemapJob>>,
+    pub shown: Option<(usize, usize)>,
+}
+
+impl SkyVariants {
+    pub fn new(hours: Vec<f32>, region: Rect) -> Self {
+        let cubes = vec![None; hours.len()];
+        let jobs = hours.iter().map(|_| None).collect();
+        Self {
+            hours,
+            region,
+            cubes,
+            jobs,
+            shown: None,
+        }
+    }
+}
+
+/// Crossfade cubemap skies between variants; sphere skies show the nearer one
+#[allow(clippy::type_complexity)]
+pub fn blend_sky_variants(
+    clock: Res<WorldClock>,
+    config: Res<WorldConfig>,
+    streaming: Res<RoomStreaming>,
+    mut skies: Query<(
+        &Skybox,
+        &mut SkyVariants,
+        Option<&MeshMaterial3d<SkyCubeMaterial>>,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+    )>,
+    mut cube_mats: ResMut<Assets<SkyCubeMaterial>>,
+    mut mats: ResMut<Assets<StandardMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (sky, mut variants, cube, sphere) in skies.iter_mut() {
+        let Some((a, b, t)) = clock.blend(&variants.hours) else {
+            continue;
+        };
+        let Some(assets) = streaming.assets(sky.room) else {
+            continue;
+        };
+
+        if let Some(handle) = cube {
+            if variants.shown != Some((a, b)) {
+                let region = variants.region;
+                for i in [a, b] {
+                    if variants.cubes[i].is_some() || variants.jobs[i].is_some() {
+                        continue;
+                    }
+                    variants.jobs[i] = assets.variants.get(i).and_then(|source| {
+                        CubemapJob::start(&images, source, region, config.max_cubemap_face)
+                    });
+                }
+                variants.shown = Some((a, b));
+            }
+            let variants = &mut *variants;
+            for (job, cube) in variants.jobs.iter_mut().zip(&mut variants.cubes) {
+                if let Some(done) = job.as_mut().and_then(|job| job.poll(&images)) {
+                    *job = None;
+                    *cube = done.map(|image| images.add(image));
+                }
+            }
+
+            let Some(mat) = cube_mats.get_mut(&handle.0) else {
+                continue;
+            };
+            // Keep showing what's there until the new pair is converted
+            let pair = match (&variants.cubes[a], &variants.cubes[b]) {
+                (Some(from), Some(to)) => Some((from, to, t)),
+                (Some(from), None) => Some((from, from, 0.0)),
+                _ => None,
+            };
+            if let Some((from, to, blend)) = pair {
+                if mat.cubemap != *from || mat.cubemap_next != *to {
+                    mat.cubemap = from.clone();
+                    mat.cubemap_next = to.clone();
+                }
+                if (mat.settings.blend - blend).abs() > 1e-3 {
+                    mat.settings.blend = blend;
+                }
+            }
+        } else if let Some(handle) = sphere {
+            let nearest = if t < 0.5 { a } else { b };
+            if variants.shown == Some((nearest, nearest)) {
+                continue;
+            }
+            variants.shown = Some((nearest, nearest));
+            if let (Some(mat), Some(texture)) =
+                (mats.get_mut(&handle.0), assets.variants.get(nearest))
+            {
+                mat.base_color_texture = Some(texture.clone());
+                mat.uv_transform = Affine2::from_scale_angle_translation(
+                    variants.region.size(),
+                    0.0,
+                    variants.region.min,
+                );
+            }
+        }
+    }
+}